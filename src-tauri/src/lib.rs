@@ -1,30 +1,732 @@
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{
-    App, AppHandle, Emitter, Manager, Runtime, WebviewWindow,
+    App, AppHandle, Emitter, Manager, Runtime, WebviewWindow, WindowEvent,
     menu::{Menu, MenuItem},
     tray::{TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState},
 };
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_notification::NotificationExt;
 
 // ─── Data Structures ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct AppEntry {
     pub name: String,
     pub path: String,
     pub icon: Option<String>,   // base64 PNG if extracted, else None
     pub category: String,
+    pub target: Option<String>,      // what a .lnk points at; None for other entries / unresolvable
+    pub args: Option<String>,        // .lnk command-line arguments
+    pub working_dir: Option<String>, // .lnk "Start in" folder
+    pub broken: bool,                // shortcut target no longer exists
+    #[serde(default)]
+    pub pinned: bool,                // in the user's pin list (set when served, not scanned)
+    #[serde(default)]
+    pub version: Option<String>,     // target exe's ProductVersion; only from get_app_details
+    #[serde(default)]
+    pub publisher: Option<String>,   // target exe's CompanyName; only from get_app_details
+}
+
+// A saved shell snippet, listed under "Commands" as `command:<name>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandEntry {
+    pub name: String,
+    pub command: String,            // passed to cmd.exe, e.g. "ipconfig /flushdns"
+    pub run_in_terminal: bool,      // visible `cmd /K` window instead of running silently
 }
 
-// Saved window position (pixels from top-left of primary monitor)
+// Saved window position. With `monitor` set, x/y are pixels from that monitor's
+// top-left; without (older saves), from the primary monitor's
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WindowPos { pub x: i32, pub y: i32 }
+pub struct WindowPos {
+    pub x: i32,
+    pub y: i32,
+    #[serde(default)]
+    pub monitor: Option<String>,   // monitor name at save time
+}
+
+// Full window configuration, saved and re-applied as one unit for session restore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub monitor: Option<String>,   // monitor name at capture time, if known
+    pub visible: bool,
+    pub always_on_top: bool,
+    #[serde(default)]
+    pub pinned: bool,              // kept open on focus loss (hide_on_blur off)
+}
 
-// Shared state: last window position so frontend can opt-in to remember it
+// Launcher size in logical pixels (scales with the monitor's DPI)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowSize { pub width: u32, pub height: u32 }
+
+// Shared state: last window position (persisted) so frontend can opt-in to remember it
 struct AppState {
     last_pos: Mutex<Option<WindowPos>>,
+    accents:  Mutex<HashMap<String, Option<String>>>,   // path → "#rrggbb", None = no icon
+    index:    Mutex<Vec<AppEntry>>,                      // latest scan result
+    dir_cache: Mutex<HashMap<PathBuf, DirScan>>,         // per scan root, for incremental rescans
+    config:   Mutex<Config>,
+    waiters:  AtomicUsize,                               // running launch_and_notify tasks
+    icon_counts: IconCounters,
+    hotkey:   Mutex<Option<Shortcut>>,                   // bound toggle hotkey (kept while suspended)
+    hotkey_suspended: AtomicBool,
+    hotkey_failed: AtomicBool,                           // startup found no free hotkey
+    hotkey_epoch: AtomicUsize,                           // bumped on suspend/resume; stale timers bail out
+    hotkey_item: Mutex<Option<MenuItem<tauri::Wry>>>,    // tray "pause/resume hotkey" entry
+    targets:  Mutex<HashMap<String, Option<String>>>,    // .lnk path → resolved target
+    locked:   Mutex<HashSet<String>>,                    // config fields pinned by machine policy
+    sizes:    Mutex<HashMap<PathBuf, (u64, u64)>>,       // install dir → (bytes, computed at)
+    sizing:   AtomicBool,                                // background sizing pass running
+    usage:    Mutex<HashMap<String, LaunchStats>>,       // path → launch history
+    store_apps: Mutex<Option<Vec<AppEntry>>>,            // Get-StartApps result; None = not queried yet
+    watcher:  Mutex<Option<notify::RecommendedWatcher>>, // Start Menu watcher, kept alive here
+    rescanning: AtomicBool,                              // background get_apps refresh running
+    aliases:  Mutex<HashMap<String, String>>,            // path → lowercase alias
+    blur_hidden_at: Mutex<Option<Instant>>,              // last hide caused by focus loss
+    skipped_dirs: Mutex<Vec<SkippedDir>>,                // roots left out of the last scan
+    scanned_at: Mutex<Option<u64>>,                      // when the index was last built (unix secs)
+    from_cache: AtomicBool,                              // last refresh reused every root's cached walk
+    hide_epoch: AtomicUsize,                             // bumped on show; stale fade-out hides bail out
+    hiding:   AtomicBool,                                // fade-out running; window counts as hidden
+}
+
+// Launch history for one app path, persisted to usage.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchStats {
+    pub count: u32,
+    pub last: u64,          // unix seconds
+}
+
+// An indexed app plus its launch history, for frecency ordering
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedApp {
+    #[serde(flatten)]
+    pub app: AppEntry,
+    pub launch_count: u32,
+    pub last_launched: Option<u64>,
+}
+
+// One search_apps result; `indices` are char positions in `name` to highlight
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub app: AppEntry,
+    pub score: i64,
+    pub indices: Vec<usize>,
+}
+
+// `index-progress` payload: files walked so far in this scan
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexProgress {
+    pub scanned: usize,
+    pub current_dir: String,
+}
+
+// A scan root left out of the last scan, and why ("timed out", "network drive", …)
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedDir {
+    pub dir: String,
+    pub reason: String,
+}
+
+// force_full_scan result; `skipped` is omitted when every root was scanned
+#[derive(Debug, Clone, Serialize)]
+pub struct FullScan {
+    pub apps: Vec<AppEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<Vec<SkippedDir>>,
+}
+
+// `launch-error` payload
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchError {
+    pub path: String,
+    pub message: String,
+}
+
+// check_for_update result
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current: String,
+    pub latest: String,
+    pub update_available: bool,
+    pub url: String,        // release page
+}
+
+// Apps installed under one drive / UNC root
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveGroup {
+    pub drive: String,      // "C:", UNC root, or "Unknown"
+    pub count: usize,
+    pub apps: Vec<AppEntry>,
+}
+
+// Approximate disk footprint of one app's install folder
+#[derive(Debug, Clone, Serialize)]
+pub struct AppSize {
+    pub name: String,
+    pub path: String,
+    pub install_dir: String,
+    pub bytes: u64,         // this app's share of the folder
+    pub shared: bool,       // folder is shared with other indexed apps
+}
+
+// Which link from an app's uninstall entry to open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UrlKind { Homepage, Support, Update }
+
+// A category present in the index, for filter chips / the sidebar
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryInfo {
+    pub name: String,
+    pub count: usize,
+    pub hidden: bool,
+}
+
+// Allowlist mode as exposed to the settings UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Allowlist {
+    pub enabled: bool,
+    pub paths: Vec<String>,
+}
+
+// Outcome of open_app_data_dir
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum DataDirMatch {
+    Opened { path: String },            // one confident match, opened in Explorer
+    Ambiguous { choices: Vec<String> }, // equally good matches; nothing opened
+    Fallback { path: String },          // no match, opened %APPDATA% itself
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity { Warning, Error }
+
+// One problem found by validate_config
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    pub field: String,
+    pub message: String,
+}
+
+// Icon extraction outcomes since startup
+#[derive(Default)]
+struct IconCounters {
+    total: AtomicUsize,
+    cached: AtomicUsize,
+    extracted: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IconStats {
+    pub total: usize,
+    pub cached: usize,      // served without running an extractor
+    pub extracted: usize,
+    pub failed: usize,
+}
+
+// User settings persisted to config.json; missing fields fall back to defaults
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct Config {
+    ui_scale: f64,
+    portableapps_root: Option<String>,   // e.g. "D:\\PortableApps"
+    enable_junk_filter: bool,            // false = keep JUNK_TERMS names (exclude_patterns still apply)
+    language: Lang,
+    allowlist_enabled: bool,             // kiosk mode: only `allowlist` paths are listed/launchable
+    allowlist: Vec<String>,
+    scan_threads: Option<usize>,         // parallel scan roots; None = adaptive to core count
+    hidden_categories: Vec<String>,      // indexed + searchable, but left out of the default browse list
+    hotkey: Option<String>,              // accelerator, e.g. "ctrl+shift+Space"; None = first free default
+    scan_dirs: Vec<String>,              // extra folders to index (shortcuts and .exe files)
+    pins: Vec<String>,                   // pinned app paths, in display order
+    hide_on_blur: bool,                  // hide the launcher when it loses focus
+    backdrop: String,                    // "acrylic" | "mica" | "tabbed" | "none"
+    skip_slow_drives: bool,              // don't scan roots on removable / network drives
+    recent: Vec<String>,                 // launched paths, most recent first (capped)
+    commands: Vec<CommandEntry>,         // user snippets shown under "Commands"
+    scan_depth: usize,                   // folder levels walked below each scan root
+    exclude_patterns: Vec<String>,       // user blocklist: names containing any of these are skipped (case-insensitive)
+    fade_ms: u64,                        // delay between `window-will-hide` and the native hide
+    launch_error_toast: bool,            // also show a toast when a launch fails
+    window_size: Option<WindowSize>,     // None = size from tauri.conf.json
+    always_on_top: bool,                 // keep the launcher above other windows
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            ui_scale: 1.0,
+            portableapps_root: None,
+            enable_junk_filter: true,
+            language: Lang::Zh,
+            allowlist_enabled: false,
+            allowlist: Vec::new(),
+            scan_threads: None,
+            hidden_categories: Vec::new(),
+            hotkey: None,
+            scan_dirs: Vec::new(),
+            pins: Vec::new(),
+            hide_on_blur: true,
+            backdrop: "acrylic".to_string(),
+            skip_slow_drives: false,
+            recent: Vec::new(),
+            commands: Vec::new(),
+            scan_depth: DEFAULT_SCAN_DEPTH,
+            exclude_patterns: Vec::new(),
+            fade_ms: DEFAULT_FADE_MS,
+            launch_error_toast: true,
+            window_size: None,
+            always_on_top: true,
+        }
+    }
+}
+
+// ─── Localization ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang { Zh, En }
+
+// Native UI strings (tray, notifications) — keep in sync with T in useSettings.ts
+struct Strings {
+    tray_show: &'static str,
+    tray_pause_hotkey: &'static str,
+    tray_resume_hotkey: &'static str,
+    tray_quit: &'static str,
+    hotkey_failed: &'static str,
+    task_finished: &'static str,        // {name}
+    task_exited: &'static str,          // {name}, {code}
+    launch_failed: &'static str,        // {name}
+}
+
+static ZH: Strings = Strings {
+    tray_show: "打开 QuickLaunch",
+    tray_pause_hotkey: "暂停热键",
+    tray_resume_hotkey: "恢复热键",
+    tray_quit: "退出",
+    hotkey_failed: "所有热键均被占用，请通过系统托盘图标打开启动器",
+    task_finished: "{name} 已结束",
+    task_exited: "{name} 已结束，退出码 {code}",
+    launch_failed: "无法打开 {name}",
+};
+
+static EN: Strings = Strings {
+    tray_show: "Open QuickLaunch",
+    tray_pause_hotkey: "Pause hotkey",
+    tray_resume_hotkey: "Resume hotkey",
+    tray_quit: "Quit",
+    hotkey_failed: "All hotkeys are taken. Open the launcher from the tray icon instead.",
+    task_finished: "{name} finished",
+    task_exited: "{name} finished, exit code {code}",
+    launch_failed: "Couldn't open {name}",
+};
+
+fn strings(lang: Lang) -> &'static Strings {
+    match lang {
+        Lang::Zh => &ZH,
+        Lang::En => &EN,
+    }
+}
+
+impl Config {
+    /// False only when allowlist mode is on (and non-empty) and `path` isn't on it.
+    fn allows(&self, path: &str) -> bool {
+        !self.allowlist_enabled
+            || self.allowlist.is_empty()
+            || self.allowlist.iter().any(|p| p.eq_ignore_ascii_case(path))
+    }
+}
+
+fn ensure_allowed(state: &AppState, path: &str) -> Result<(), String> {
+    if state.config.lock().unwrap().allows(path) { Ok(()) } else {
+        Err(format!("Forbidden: {path} is not on the allowlist"))
+    }
+}
+
+fn current_strings(state: &AppState) -> &'static Strings {
+    strings(state.config.lock().unwrap().language)
+}
+
+// Entries found under one scan root, tagged with the root's fingerprint
+#[derive(Clone, Serialize, Deserialize)]
+struct DirScan {
+    hash: u64,
+    apps: Vec<AppEntry>,
+}
+
+// apps.json: last index plus per-root scans, so a cold start can skip the walk
+#[derive(Serialize, Deserialize)]
+struct AppCache {
+    roots: HashMap<PathBuf, DirScan>,
+    apps: Vec<AppEntry>,
+    #[serde(default)]
+    scanned_at: u64,        // unix seconds of the last index build
+}
+
+// last_scan_info result
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanInfo {
+    pub cache_age_secs: Option<u64>,    // since the index was last built; None = never
+    pub from_cache: bool,               // no scan root changed, so no folder was re-walked
+}
+
+// ─── Persistence ───────────────────────────────────────────────────────────────
+
+const CONFIG_FILE: &str = "config.json";
+const CONFIG_BACKUP_FILE: &str = "config.invalid.json";
+const FIRST_SEEN_FILE: &str = "first_seen.json";
+const WINDOW_POS_FILE: &str = "window_pos.json";
+const USAGE_FILE: &str = "usage.json";
+const APP_CACHE_FILE: &str = "apps.json";
+const ALIASES_FILE: &str = "aliases.json";
+const RECENTLY_INSTALLED: &str = "Recently Installed";
+// Length of the recent-launch list kept in config.json
+const RECENT_LIMIT: usize = 50;
+// Bumped whenever a settings bundle from an older build can't be read as-is
+const SETTINGS_SCHEMA: u64 = 1;
+
+// export_settings / import_settings payload: everything the user configured
+#[derive(Serialize, Deserialize)]
+struct SettingsBundle {
+    schema: u64,
+    config: Config,
+    aliases: HashMap<String, String>,   // path → alias
+}
+
+fn config_file<R: Runtime>(app: &AppHandle<R>, name: &str) -> Option<PathBuf> {
+    app.path().app_config_dir().ok().map(|d| d.join(name))
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let raw = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    std::fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+type JsonMap = serde_json::Map<String, serde_json::Value>;
+
+/// Machine-wide policy for managed deployments (%PROGRAMDATA%\QuickLaunch\config.json).
+/// Every field it sets overrides the user's value and can't be changed from the app.
+fn machine_config_file() -> Option<PathBuf> {
+    std::env::var("PROGRAMDATA").ok().map(|d| PathBuf::from(d).join("QuickLaunch").join(CONFIG_FILE))
+}
+
+/// User config overlaid with the machine policy; also returns the locked field names.
+fn load_config<R: Runtime>(app: &AppHandle<R>) -> (Config, HashSet<String>) {
+    let user: JsonMap = config_file(app, CONFIG_FILE).and_then(|f| read_user_config(&f)).unwrap_or_default();
+    let policy: JsonMap = machine_config_file().and_then(|f| read_json(&f)).unwrap_or_default();
+    let locked = policy.keys().cloned().collect();
+
+    let mut merged = user;
+    merged.extend(policy);
+    (config_from_fields(merged), locked)
+}
+
+/// The user config as JSON. A file that isn't a JSON object is moved aside to
+/// CONFIG_BACKUP_FILE first, so the next save can't overwrite the only copy.
+fn read_user_config(file: &Path) -> Option<JsonMap> {
+    if !file.exists() { return None; }
+    let parsed = read_json(file);
+    if parsed.is_none() {
+        let backup = file.with_file_name(CONFIG_BACKUP_FILE);
+        eprintln!("[QuickLaunch] Unreadable config, moved to {}", backup.display());
+        if let Err(e) = std::fs::rename(file, &backup) {
+            eprintln!("[QuickLaunch] Couldn't move the unreadable config aside: {e}");
+        }
+    }
+    parsed
+}
+
+/// Build a Config one field at a time: a field with a bad value falls back to
+/// its default instead of taking every other setting down with it.
+fn config_from_fields(fields: JsonMap) -> Config {
+    let serde_json::Value::Object(mut good) = serde_json::to_value(Config::default()).unwrap_or_default() else {
+        return Config::default();
+    };
+    for (key, value) in fields {
+        let mut trial = good.clone();
+        trial.insert(key.clone(), value);
+        match serde_json::from_value::<Config>(serde_json::Value::Object(trial.clone())) {
+            Ok(_) => good = trial,
+            Err(e) => eprintln!("[QuickLaunch] Invalid config field {key}, using its default: {e}"),
+        }
+    }
+    serde_json::from_value(serde_json::Value::Object(good)).unwrap_or_default()
+}
+
+/// Write the user config. Locked fields keep whatever the user file already
+/// had, so policy values never leak into it.
+fn save_config<R: Runtime>(app: &AppHandle<R>, config: &Config) -> Result<(), String> {
+    let file = config_file(app, CONFIG_FILE).ok_or("config directory unavailable")?;
+    let locked = app.state::<AppState>().locked.lock().unwrap().clone();
+    if locked.is_empty() { return write_json(&file, config); }
+
+    let previous: JsonMap = read_json(&file).unwrap_or_default();
+    let serde_json::Value::Object(mut out) = serde_json::to_value(config).map_err(|e| e.to_string())? else {
+        return Err("config did not serialize to an object".into());
+    };
+    for field in &locked {
+        if let Some(v) = previous.get(field) {
+            out.insert(field.clone(), v.clone());
+        } else {
+            out.remove(field);
+        }
+    }
+    write_json(&file, &out)
+}
+
+fn ensure_unlocked(state: &AppState, field: &str) -> Result<(), String> {
+    if state.locked.lock().unwrap().contains(field) {
+        return Err(format!("Locked: {field} is managed by machine policy"));
+    }
+    Ok(())
+}
+
+/// Apply `f` to the live config and persist it, unless `field` is locked.
+fn update_config<F: FnOnce(&mut Config)>(app: &AppHandle, field: &str, f: F) -> Result<Config, String> {
+    let state = app.state::<AppState>();
+    ensure_unlocked(&state, field)?;
+    let config = {
+        let mut cfg = state.config.lock().unwrap();
+        f(&mut cfg);
+        cfg.clone()
+    };
+    save_config(app, &config)?;
+    Ok(config)
+}
+
+fn issue(severity: Severity, field: &str, message: String) -> ConfigIssue {
+    ConfigIssue { severity, field: field.to_string(), message }
+}
+
+/// Dangling or out-of-range settings, e.g. from a hand-edited config.json.
+/// References to apps are checked against `index`; pass an empty one (e.g.
+/// before the first scan) to skip those checks.
+fn config_issues(cfg: &Config, index: &[AppEntry]) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    if !(UI_SCALE_MIN..=UI_SCALE_MAX).contains(&cfg.ui_scale) {
+        issues.push(issue(Severity::Error, "ui_scale",
+            format!("{} is outside {UI_SCALE_MIN}–{UI_SCALE_MAX}", cfg.ui_scale)));
+    }
+    if let Some(root) = &cfg.portableapps_root {
+        if !Path::new(root).is_dir() {
+            issues.push(issue(Severity::Warning, "portableapps_root",
+                format!("directory does not exist: {root}")));
+        }
+    }
+    for dir in cfg.scan_dirs.iter().filter(|d| !Path::new(d).is_dir()) {
+        issues.push(issue(Severity::Warning, "scan_dirs", format!("directory does not exist: {dir}")));
+    }
+    if let Some(accel) = &cfg.hotkey {
+        if accel.parse::<Shortcut>().is_err() {
+            issues.push(issue(Severity::Error, "hotkey", format!("not a valid shortcut: {accel}")));
+        }
+    }
+    if !(1..=MAX_SCAN_DEPTH).contains(&cfg.scan_depth) {
+        issues.push(issue(Severity::Error, "scan_depth",
+            format!("{} is outside 1–{MAX_SCAN_DEPTH}", cfg.scan_depth)));
+    }
+    if cfg.fade_ms > MAX_FADE_MS {
+        issues.push(issue(Severity::Error, "fade_ms", format!("{} is above {MAX_FADE_MS}", cfg.fade_ms)));
+    }
+    if let Some(size) = cfg.window_size {
+        if clamp_window_size(size) != size {
+            issues.push(issue(Severity::Error, "window_size",
+                format!("{}×{} is outside {}×{}–{}×{}", size.width, size.height,
+                    MIN_WINDOW_SIZE.0, MIN_WINDOW_SIZE.1, MAX_WINDOW_SIZE.0, MAX_WINDOW_SIZE.1)));
+        }
+    }
+    if backdrop_type(&cfg.backdrop).is_none() {
+        issues.push(issue(Severity::Error, "backdrop", format!("unknown backdrop: {}", cfg.backdrop)));
+    }
+    if !index.is_empty() {
+        for pin in cfg.pins.iter().filter(|p| !is_indexed(index, p)) {
+            issues.push(issue(Severity::Warning, "pins", format!("pinned app is no longer installed: {pin}")));
+        }
+        for cat in cfg.hidden_categories.iter().filter(|c| !has_category(index, c)) {
+            issues.push(issue(Severity::Warning, "hidden_categories", format!("no indexed app is in category: {cat}")));
+        }
+    }
+    issues
+}
+
+/// Aliases (kept in aliases.json, not config.json) for apps missing from `index`.
+fn alias_issues(aliases: &HashMap<String, String>, index: &[AppEntry]) -> Vec<ConfigIssue> {
+    if index.is_empty() { return Vec::new(); }
+    let mut dead: Vec<&String> = aliases.keys().filter(|p| !is_indexed(index, p)).collect();
+    dead.sort();
+    dead.into_iter()
+        .map(|p| issue(Severity::Warning, "aliases", format!("alias \"{}\" is for an app that is no longer installed: {p}", aliases[p])))
+        .collect()
+}
+
+fn is_indexed(index: &[AppEntry], path: &str) -> bool {
+    index.iter().any(|a| a.path == path)
+}
+
+fn has_category(index: &[AppEntry], category: &str) -> bool {
+    index.iter().any(|a| a.category == category)
+}
+
+/// Fix what `config_issues` reports: clamp bad values, drop dead references.
+/// Fields in `locked` are left alone.
+fn repair(cfg: &mut Config, locked: &HashSet<String>, index: &[AppEntry]) -> Vec<ConfigIssue> {
+    let mut issues = config_issues(cfg, index);
+    issues.retain(|i| !locked.contains(&i.field));
+    for i in &issues {
+        match i.field.as_str() {
+            "ui_scale" => {
+                cfg.ui_scale = if cfg.ui_scale.is_finite() {
+                    cfg.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX)
+                } else {
+                    Config::default().ui_scale
+                };
+            }
+            "portableapps_root" => cfg.portableapps_root = None,
+            "hotkey" => cfg.hotkey = None,
+            "backdrop" => cfg.backdrop = Config::default().backdrop,
+            "scan_depth" => cfg.scan_depth = cfg.scan_depth.clamp(1, MAX_SCAN_DEPTH),
+            "fade_ms" => cfg.fade_ms = MAX_FADE_MS,
+            "window_size" => cfg.window_size = cfg.window_size.map(clamp_window_size),
+            "scan_dirs" => cfg.scan_dirs.retain(|d| Path::new(d).is_dir()),
+            "pins" => cfg.pins.retain(|p| is_indexed(index, p)),
+            "hidden_categories" => cfg.hidden_categories.retain(|c| has_category(index, c)),
+            _ => {}
+        }
+    }
+    issues
+}
+
+fn load_window_pos<R: Runtime>(app: &AppHandle<R>) -> Option<WindowPos> {
+    read_json(&config_file(app, WINDOW_POS_FILE)?)
+}
+
+fn persist_window_pos<R: Runtime>(app: &AppHandle<R>, pos: &WindowPos) -> Result<(), String> {
+    let file = config_file(app, WINDOW_POS_FILE).ok_or("config directory unavailable")?;
+    write_json(&file, pos)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Seed the index and per-root scan cache from apps.json. Roots whose
+/// fingerprint (sub-folder mtimes) changed since are re-walked on the next scan.
+fn load_app_cache<R: Runtime>(app: &AppHandle<R>, state: &AppState) {
+    let Some(cache) = config_file(app, APP_CACHE_FILE).and_then(|f| read_json::<AppCache>(&f)) else { return };
+    *state.dir_cache.lock().unwrap() = cache.roots;
+    *state.index.lock().unwrap() = cache.apps;
+    *state.scanned_at.lock().unwrap() = Some(cache.scanned_at).filter(|&t| t > 0);
+}
+
+fn save_app_cache<R: Runtime>(app: &AppHandle<R>, state: &AppState) {
+    let Some(file) = config_file(app, APP_CACHE_FILE) else { return };
+    let cache = AppCache {
+        roots: state.dir_cache.lock().unwrap().clone(),
+        apps: state.index.lock().unwrap().clone(),
+        scanned_at: state.scanned_at.lock().unwrap().unwrap_or(0),
+    };
+    let _ = write_json(&file, &cache);
+}
+
+fn load_usage<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, LaunchStats> {
+    config_file(app, USAGE_FILE).and_then(|f| read_json(&f)).unwrap_or_default()
+}
+
+fn save_usage<R: Runtime>(app: &AppHandle<R>, usage: &HashMap<String, LaunchStats>) {
+    if let Some(file) = config_file(app, USAGE_FILE) {
+        let _ = write_json(&file, usage);
+    }
+}
+
+/// Write everything kept in memory (index cache, window position, launch
+/// history, settings) to disk. Run before quitting.
+fn flush_state<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<AppState>();
+    save_app_cache(app, &state);
+    if let Some(pos) = state.last_pos.lock().unwrap().clone() {
+        let _ = persist_window_pos(app, &pos);
+    }
+    save_usage(app, &state.usage.lock().unwrap());
+    let config = state.config.lock().unwrap().clone();
+    let _ = save_config(app, &config);
+}
+
+/// Flush state and exit.
+fn quit(app: &AppHandle) {
+    flush_state(app);
+    app.exit(0);
+}
+
+/// Bump the launch count for `path`, move it to the front of the recent list
+/// and persist both.
+fn record_launch<R: Runtime>(app: &AppHandle<R>, path: &str) {
+    let state = app.state::<AppState>();
+    let usage = {
+        let mut usage = state.usage.lock().unwrap();
+        let stats = usage.entry(path.to_string()).or_default();
+        stats.count += 1;
+        stats.last = now_secs();
+        usage.clone()
+    };
+    save_usage(app, &usage);
+    let config = {
+        let mut cfg = state.config.lock().unwrap();
+        cfg.recent.retain(|p| p != path);
+        cfg.recent.insert(0, path.to_string());
+        cfg.recent.truncate(RECENT_LIMIT);
+        cfg.clone()
+    };
+    let _ = save_config(app, &config);
+}
+
+fn load_aliases<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, String> {
+    config_file(app, ALIASES_FILE).and_then(|f| read_json(&f)).unwrap_or_default()
+}
+
+fn save_aliases<R: Runtime>(app: &AppHandle<R>, aliases: &HashMap<String, String>) -> Result<(), String> {
+    let file = config_file(app, ALIASES_FILE).ok_or("no config dir")?;
+    write_json(&file, aliases)
+}
+
+/// Record when each app path was first indexed.
+/// The very first run stamps everything with 0 ("unknown") so an existing
+/// install doesn't show up as freshly installed.
+fn record_first_seen<R: Runtime>(app: &AppHandle<R>, apps: &[AppEntry]) -> HashMap<String, u64> {
+    let Some(file) = config_file(app, FIRST_SEEN_FILE) else { return HashMap::new() };
+    let existing: Option<HashMap<String, u64>> = read_json(&file);
+    let stamp = if existing.is_some() { now_secs() } else { 0 };
+    let mut seen = existing.unwrap_or_default();
+    let before = seen.len();
+    for a in apps {
+        seen.entry(a.path.clone()).or_insert(stamp);
+    }
+    if seen.len() != before || before == 0 {
+        let _ = write_json(&file, &seen);
+    }
+    seen
 }
 
 // ─── App Indexer ───────────────────────────────────────────────────────────────
@@ -43,197 +745,3630 @@ fn get_start_menu_dirs() -> Vec<PathBuf> {
     dirs
 }
 
-fn scan_apps(dirs: &[PathBuf]) -> Vec<AppEntry> {
+// Shortcut files picked up in every scan root (.exe only in user scan dirs)
+const SHORTCUT_EXTS: &[&str] = &["lnk", "url", "appref-ms"];
+
+fn is_shortcut_file(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str())
+        .is_some_and(|e| SHORTCUT_EXTS.iter().any(|x| e.eq_ignore_ascii_case(x)))
+}
+
+// Built-in junk filter (enable_junk_filter): names that are almost never what the user wants to launch
+const JUNK_TERMS: &[&str] = &["uninstall", "readme", "help", "manual"];
+const DEFAULT_SCAN_DEPTH: usize = 5;
+const MAX_SCAN_DEPTH: usize = 16;
+
+// How deep to walk and which names to drop, from the config
+#[derive(Debug, Clone, Hash)]
+struct ScanRules {
+    depth: usize,
+    exclude: Vec<String>,   // lowercase; user patterns, plus JUNK_TERMS when the junk filter is on
+}
+
+impl ScanRules {
+    fn from_config(cfg: &Config) -> Self {
+        let junk = JUNK_TERMS.iter().filter(|_| cfg.enable_junk_filter).map(|t| t.to_string());
+        let exclude = cfg.exclude_patterns.iter()
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .chain(junk)
+            .collect();
+        ScanRules { depth: cfg.scan_depth.clamp(1, MAX_SCAN_DEPTH), exclude }
+    }
+
+    fn is_junk(&self, name: &str) -> bool {
+        let lower = name.to_lowercase();
+        self.exclude.iter().any(|t| lower.contains(t))
+    }
+}
+
+/// Index the shortcuts under `dir`; with `exes`, .exe files count as apps too.
+/// `on_entry` sees the folder of every file or folder walked (for progress).
+fn scan_dir(dir: &Path, rules: &ScanRules, exes: bool, on_entry: &(dyn Fn(&Path) + Sync)) -> Vec<AppEntry> {
     let mut apps = Vec::new();
-    for dir in dirs {
-        if !dir.exists() { continue; }
-        for entry in walkdir::WalkDir::new(dir).max_depth(5).follow_links(true)
-            .into_iter().filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.extension().and_then(|e| e.to_str()) != Some("lnk") { continue; }
-            let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
-            let lower = name.to_lowercase();
-            if lower.contains("uninstall") || lower.contains("readme")
-                || lower.contains("help") || lower.contains("manual") { continue; }
-            let category = dir.file_name().and_then(|s| s.to_str()).unwrap_or("Other").to_string();
-            apps.push(AppEntry { name, path: path.to_string_lossy().to_string(), icon: None, category });
-        }
-    }
-    let mut seen = std::collections::HashSet::new();
-    apps.retain(|a| seen.insert(a.name.clone()));
-    apps.sort_by(|a, b| a.name.cmp(&b.name));
+    if !dir.exists() { return apps; }
+    for entry in walkdir::WalkDir::new(dir).max_depth(rules.depth).follow_links(true)
+        .into_iter().filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        on_entry(path.parent().unwrap_or(dir));
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        if !is_shortcut_file(path) && !(exes && ext == "exe") { continue; }
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+        if rules.is_junk(&name) { continue; }
+        let category = dir.file_name().and_then(|s| s.to_str()).unwrap_or("Other").to_string();
+        // ClickOnce .appref-ms files aren't shell links; ShellExecute on the file
+        // itself hands them to dfshim
+        if ext == "exe" || ext == "appref-ms" {
+            apps.push(AppEntry { name, path: path.to_string_lossy().to_string(), category, ..Default::default() });
+            continue;
+        }
+        if ext == "url" {
+            // Internet shortcuts are indexed by the URL they open
+            if let Some(url) = read_url_shortcut(path) {
+                apps.push(AppEntry { name, path: url, category, ..Default::default() });
+            }
+            continue;
+        }
+        let link = read_shortcut(&path.to_string_lossy()).unwrap_or_default();
+        let broken = link.target.as_deref().is_some_and(|t| !Path::new(t).exists());
+        apps.push(AppEntry {
+            name,
+            path: path.to_string_lossy().to_string(),
+            icon: None,
+            category,
+            target: link.target,
+            args: link.args,
+            working_dir: link.working_dir,
+            broken,
+            pinned: false,
+            version: None,
+            publisher: None,
+        });
+    }
     apps
 }
 
-// ─── Icon Extraction ───────────────────────────────────────────────────────────
+/// URL= of an `[InternetShortcut]` (.url) file, if it's one we can open.
+fn read_url_shortcut(file: &Path) -> Option<String> {
+    let text = String::from_utf8_lossy(&std::fs::read(file).ok()?).into_owned();
+    ini_value(&text, "InternetShortcut", "URL").filter(|u| is_url_entry(u))
+}
 
-/// Extract the icon from a .lnk file (or its target exe) as a base64 PNG.
-/// Uses PowerShell + System.Drawing — available on all Windows editions.
-/// Returns None on any failure so the frontend can fall back to the color avatar.
+/// How usable an entry is when deduplicating: a shortcut with a dangling
+/// target < one whose target couldn't be read < anything that works.
+fn entry_quality(a: &AppEntry) -> u8 {
+    if a.broken { 0 } else if is_lnk(&a.path) && a.target.is_none() { 1 } else { 2 }
+}
+
+/// Re-check every shortcut's target. Roots reused from the scan cache keep the
+/// `broken` flag from when they were walked, and uninstalling an app doesn't
+/// touch the Start Menu folder holding its shortcut.
+fn refresh_broken(apps: &mut [AppEntry]) {
+    for a in apps.iter_mut() {
+        if let Some(target) = &a.target {
+            a.broken = !Path::new(target).exists();
+        }
+    }
+}
+
+/// Dedup by name and sort alphabetically. On a name collision the entry with
+/// a working target wins; between equally good ones the first wins, and
+/// callers pass sources in precedence order: all-users Start Menu
+/// (PROGRAMDATA), then the per-user Start Menu, then the Desktop, then extra
+/// folders, PortableApps, Store, PATH and the rest.
+fn finish_index(mut apps: Vec<AppEntry>) -> Vec<AppEntry> {
+    refresh_broken(&mut apps);
+    let mut slot: HashMap<String, usize> = HashMap::new();
+    let mut kept: Vec<AppEntry> = Vec::new();
+    for app in apps {
+        match slot.get(&app.name) {
+            Some(&i) => if entry_quality(&app) > entry_quality(&kept[i]) { kept[i] = app; },
+            None => {
+                slot.insert(app.name.clone(), kept.len());
+                kept.push(app);
+            }
+        }
+    }
+    kept.sort_by(|a, b| a.name.cmp(&b.name));
+    kept
+}
+
+/// Fingerprint of a scan root: every sub-directory's path + mtime, plus the
+/// scan rules. Adding or removing a shortcut bumps its parent folder's mtime,
+/// so this changes whenever the set of files under `dir` does, without reading them.
+/// It doesn't see a shortcut's target changing or disappearing; `finish_index`
+/// re-checks targets (`refresh_broken`) for entries reused on an unchanged hash.
+fn dir_hash(dir: &Path, rules: &ScanRules) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut h = std::collections::hash_map::DefaultHasher::new();
+    rules.hash(&mut h);
+    for entry in walkdir::WalkDir::new(dir).max_depth(rules.depth).follow_links(true)
+        .into_iter().filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+    {
+        entry.path().hash(&mut h);
+        if let Ok(m) = entry.metadata().and_then(|m| m.modified()) {
+            m.hash(&mut h);
+        }
+    }
+    h.finish()
+}
+
+/// Default for `scan_threads`: half the cores, between 1 and 4, so a cold
+/// scan doesn't starve whatever the user is doing in the foreground.
+fn default_scan_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get() / 2).clamp(1, 4)
+}
+
+// A folder to index
+struct ScanRoot {
+    dir: PathBuf,
+    exes: bool,     // also list .exe files directly (user-added folders)
+}
+
+/// Start Menu / Desktop roots followed by the user's extra folders.
+fn scan_roots(cfg: &Config) -> Vec<ScanRoot> {
+    let mut roots: Vec<ScanRoot> = get_start_menu_dirs().into_iter()
+        .map(|dir| ScanRoot { dir, exes: false })
+        .collect();
+    for dir in cfg.scan_dirs.iter().map(PathBuf::from) {
+        if !roots.iter().any(|r| r.dir == dir) {
+            roots.push(ScanRoot { dir, exes: true });
+        }
+    }
+    roots
+}
+
+/// Hash a scan root and walk it only if the hash differs from `cached_hash`;
+/// None means the cached entries are still current.
+fn rescan_root(
+    dir: &Path,
+    rules: &ScanRules,
+    exes: bool,
+    cached_hash: Option<u64>,
+    on_entry: &(dyn Fn(&Path) + Sync),
+) -> (u64, Option<Vec<AppEntry>>) {
+    let hash = dir_hash(dir, rules);
+    (hash, (cached_hash != Some(hash)).then(|| scan_dir(dir, rules, exes, on_entry)))
+}
+
+// Longest a single root may take to hash + walk before it's abandoned
+const SCAN_DIR_BUDGET: Duration = Duration::from_millis(1500);
+
+/// "removable" / "network" if `dir` lives on such a drive (GetDriveTypeW).
 #[cfg(target_os = "windows")]
-fn extract_icon_base64(lnk_path: &str) -> Option<String> {
-    // PowerShell script: resolve .lnk target → extract icon → return base64 PNG
-    let script = format!(
-        r#"
-$ErrorActionPreference = 'Stop'
-try {{
-    Add-Type -Assembly System.Drawing
-    $shell = New-Object -ComObject WScript.Shell
-    $lnk = $shell.CreateShortcut('{lnk}')
-    $target = $lnk.TargetPath
-    if (-not $target -or -not (Test-Path $target)) {{ $target = '{lnk}' }}
-    $icon = [System.Drawing.Icon]::ExtractAssociatedIcon($target)
-    if (-not $icon) {{ exit 1 }}
-    $bmp = $icon.ToBitmap()
-    $ms = New-Object System.IO.MemoryStream
-    $bmp.Save($ms, [System.Drawing.Imaging.ImageFormat]::Png)
-    [Convert]::ToBase64String($ms.ToArray())
-}} catch {{ exit 1 }}
-"#,
-        lnk = lnk_path.replace("'", "''")
-    );
+fn slow_drive_kind(dir: &Path) -> Option<&'static str> {
+    use std::path::Component;
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+    let Some(Component::Prefix(prefix)) = dir.components().next() else { return None };
+    let root = format!("{}\\", prefix.as_os_str().to_string_lossy());
+    match unsafe { GetDriveTypeW(&HSTRING::from(root)) } {
+        2 => Some("removable"), // DRIVE_REMOVABLE
+        4 => Some("network"),   // DRIVE_REMOTE
+        _ => None,
+    }
+}
 
-    let out = std::process::Command::new("powershell")
-        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
-        .output()
-        .ok()?;
+#[cfg(not(target_os = "windows"))]
+fn slow_drive_kind(_dir: &Path) -> Option<&'static str> { None }
+
+/// Scan all roots, re-walking only those whose `dir_hash` changed since the
+/// previous call; unchanged roots reuse their cached entries. Up to `threads`
+/// roots are hashed/walked at once. A root that takes longer than
+/// `SCAN_DIR_BUDGET` is abandoned (its last cached entries are kept), and with
+/// `skip_slow_drives` roots on removable or network drives aren't touched.
+fn scan_apps<R: Runtime>(
+    app: &AppHandle<R>,
+    roots: &[ScanRoot],
+    rules: &ScanRules,
+    threads: usize,
+    skip_slow_drives: bool,
+    cache: &mut HashMap<PathBuf, DirScan>,
+) -> (Vec<AppEntry>, Vec<SkippedDir>) {
+    let scanned = std::sync::Arc::new(AtomicUsize::new(0));
+    let mut skipped = Vec::new();
+    // Per root: fresh (hash, walk) result, or None with whether stale cache may be used
+    let mut results: Vec<Result<(u64, Option<Vec<AppEntry>>), bool>> = Vec::new();
+    for batch in roots.chunks(threads.max(1)) {
+        let pending: Vec<Result<_, &str>> = batch.iter().map(|root| {
+            if skip_slow_drives {
+                if let Some(kind) = slow_drive_kind(&root.dir) { return Err(kind); }
+            }
+            let cached_hash = cache.get(&root.dir).map(|c| c.hash);
+            let (dir, exes, app, scanned) = (root.dir.clone(), root.exes, app.clone(), scanned.clone());
+            let rules = rules.clone();
+            let (tx, rx) = std::sync::mpsc::channel();
+            // Detached so a hung network share can be left behind
+            std::thread::spawn(move || {
+                let report = |d: &Path| {
+                    let n = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+                    if n % PROGRESS_EVERY == 0 {
+                        let _ = app.emit("index-progress", IndexProgress {
+                            scanned: n,
+                            current_dir: d.to_string_lossy().to_string(),
+                        });
+                    }
+                };
+                let _ = tx.send(rescan_root(&dir, &rules, exes, cached_hash, &report));
+            });
+            Ok(rx)
+        }).collect();
+
+        let deadline = Instant::now() + SCAN_DIR_BUDGET;
+        for (root, p) in batch.iter().zip(pending) {
+            use std::sync::mpsc::RecvTimeoutError;
+            let reason = match p {
+                Err(kind) => format!("{kind} drive"),
+                Ok(rx) => match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(result) => { results.push(Ok(result)); continue; }
+                    // Worker panicked: treat as an empty folder, like before
+                    Err(RecvTimeoutError::Disconnected) => { results.push(Ok((0, Some(Vec::new())))); continue; }
+                    Err(RecvTimeoutError::Timeout) => "timed out".to_string(),
+                },
+            };
+            eprintln!("[QuickLaunch] Skipped scan root {}: {reason}", root.dir.display());
+            results.push(Err(reason == "timed out"));
+            skipped.push(SkippedDir { dir: root.dir.to_string_lossy().to_string(), reason });
+        }
+    }
+
+    let mut apps = Vec::new();
+    for (root, result) in roots.iter().zip(results) {
+        match result {
+            Ok((hash, Some(walked))) => { cache.insert(root.dir.clone(), DirScan { hash, apps: walked }); }
+            Ok((_, None)) | Err(true) => {}
+            Err(false) => continue,
+        }
+        if let Some(c) = cache.get(&root.dir) {
+            apps.extend(c.apps.iter().cloned());
+        }
+    }
+    cache.retain(|d, _| roots.iter().any(|r| r.dir == *d));
+    (finish_index(apps), skipped)
+}
+
+/// Read `key` from `[section]` of an INI-style file.
+fn ini_value(text: &str, section: &str, key: &str) -> Option<String> {
+    let mut in_section = false;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            in_section = line[1..line.len() - 1].eq_ignore_ascii_case(section);
+        } else if in_section {
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim().eq_ignore_ascii_case(key) {
+                    return Some(v.trim().to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Apps under a PortableApps.com platform folder: `<root>\<App>\App\AppInfo\appinfo.ini`
+/// gives the friendly name and launcher exe, and the bundled appicon files the icon.
+fn scan_portableapps(root: &Path) -> Vec<AppEntry> {
+    let Ok(dirs) = std::fs::read_dir(root) else { return Vec::new() };
+    let mut apps = Vec::new();
+    for dir in dirs.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()) {
+        let info = dir.join("App").join("AppInfo");
+        let Ok(raw) = std::fs::read(info.join("appinfo.ini")) else { continue };
+        let text = String::from_utf8_lossy(&raw);
+        let text = text.trim_start_matches('\u{feff}');
+
+        let dir_name = dir.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
+        let exe = ini_value(text, "Control", "Start").unwrap_or_else(|| format!("{dir_name}.exe"));
+        let exe = dir.join(exe);
+        if !exe.exists() { continue; }
+
+        let name = ini_value(text, "Details", "Name").unwrap_or(dir_name);
+        apps.push(AppEntry {
+            name,
+            path: exe.to_string_lossy().to_string(),
+            icon: portable_icon(&info),
+            category: "PortableApps".to_string(),
+            ..Default::default()
+        });
+    }
+    apps
+}
+
+/// Best bundled icon in an AppInfo folder as base64 PNG: the largest
+/// appicon_<n>.png, else an appicon*.ico re-encoded to PNG.
+fn portable_icon(info: &Path) -> Option<String> {
+    use base64::Engine;
+    let b64 = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+
+    for size in ["128", "75", "32", "16"] {
+        if let Ok(bytes) = std::fs::read(info.join(format!("appicon_{size}.png"))) {
+            return Some(b64(&bytes));
+        }
+    }
+    let ico = std::fs::read_dir(info).ok()?.filter_map(|e| e.ok()).map(|e| e.path()).find(|p| {
+        let name = p.file_name().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
+        name.starts_with("appicon") && name.ends_with(".ico")
+    })?;
+    let img = image::open(ico).ok()?;
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).ok()?;
+    Some(b64(&png))
+}
+
+const STORE_CATEGORY: &str = "Store";
+const APPS_FOLDER: &str = "shell:AppsFolder\\";
+
+/// Packaged (UWP / Microsoft Store) apps from `Get-StartApps`. Only entries
+/// with a package AppUserModelID (`Family!App`) are kept; desktop apps already
+/// come from their Start Menu shortcuts. Paths are `shell:AppsFolder\<AUMID>`.
+#[cfg(target_os = "windows")]
+fn scan_uwp_apps(rules: &ScanRules) -> Vec<AppEntry> {
+    let script = r#"
+[Console]::OutputEncoding = [System.Text.Encoding]::UTF8
+Get-StartApps | ForEach-Object { "$($_.Name)`t$($_.AppID)" }
+"#;
+    let Ok(out) = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&out.stdout).lines()
+        .filter_map(|l| l.trim().split_once('\t'))
+        .filter(|(name, id)| id.contains('!') && !rules.is_junk(name))
+        .map(|(name, id)| AppEntry {
+            name: name.to_string(),
+            path: format!("{APPS_FOLDER}{id}"),
+            icon: None,
+            category: STORE_CATEGORY.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn scan_uwp_apps(_: &ScanRules) -> Vec<AppEntry> { Vec::new() }
+
+const BOOKMARKS_CATEGORY: &str = "Bookmarks";
+
+/// Web bookmarks from the default Chrome / Edge / Firefox profiles, as entries
+/// whose `path` is the URL. Browsers that aren't installed are skipped.
+fn scan_bookmarks() -> Vec<AppEntry> {
+    let mut marks: Vec<(String, String)> = Vec::new();
+    if let Ok(local) = std::env::var("LOCALAPPDATA") {
+        for browser in [r"Google\Chrome", r"Microsoft\Edge"] {
+            let file = Path::new(&local).join(browser).join("User Data").join("Default").join("Bookmarks");
+            marks.extend(chromium_bookmarks(&file));
+        }
+    }
+    if let Some(places) = firefox_places_file() {
+        marks.extend(firefox_bookmarks(&places));
+    }
+
+    let mut seen = HashSet::new();
+    marks.into_iter()
+        .filter(|(title, url)| !title.trim().is_empty() && is_web_url(url) && seen.insert(url.clone()))
+        .map(|(name, path)| AppEntry { name, path, category: BOOKMARKS_CATEGORY.to_string(), ..Default::default() })
+        .collect()
+}
+
+/// (title, url) pairs from a Chromium `Bookmarks` JSON file.
+fn chromium_bookmarks(file: &Path) -> Vec<(String, String)> {
+    fn walk(node: &serde_json::Value, out: &mut Vec<(String, String)>) {
+        if node["type"] == "url" {
+            if let (Some(name), Some(url)) = (node["name"].as_str(), node["url"].as_str()) {
+                out.push((name.to_string(), url.to_string()));
+            }
+        }
+        for child in node["children"].as_array().into_iter().flatten() {
+            walk(child, out);
+        }
+    }
+    let Some(doc) = read_json::<serde_json::Value>(file) else { return Vec::new() };
+    let mut out = Vec::new();
+    for root in doc["roots"].as_object().into_iter().flat_map(|r| r.values()) {
+        walk(root, &mut out);
+    }
+    out
+}
+
+/// places.sqlite of Firefox's default profile: the profile the install uses
+/// (`[Install…] Default=`), else the one marked `Default=1`.
+fn firefox_places_file() -> Option<PathBuf> {
+    let root = PathBuf::from(std::env::var("APPDATA").ok()?).join("Mozilla").join("Firefox");
+    let ini = std::fs::read_to_string(root.join("profiles.ini")).ok()?;
+    let (mut install_default, mut marked_default) = (None, None);
+    let (mut section, mut path, mut is_default) = (String::new(), None::<String>, false);
+    for line in ini.lines().map(str::trim).chain(std::iter::once("[end]")) {
+        if line.starts_with('[') {
+            if section.starts_with("Profile") && is_default && marked_default.is_none() {
+                marked_default = path.take();
+            }
+            section = line.trim_matches(|c| c == '[' || c == ']').to_string();
+            (path, is_default) = (None, false);
+        } else if let Some((k, v)) = line.split_once('=') {
+            match (k.trim(), section.starts_with("Install")) {
+                ("Default", true) if install_default.is_none() => install_default = Some(v.trim().to_string()),
+                ("Default", false) => is_default = v.trim() == "1",
+                ("Path", false) => path = Some(v.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+    let rel = install_default.or(marked_default)?;
+    // Relative paths use forward slashes; absolute ones are stored as-is
+    let profile = if Path::new(&rel).is_absolute() { PathBuf::from(&rel) } else { root.join(rel.replace('/', "\\")) };
+    Some(profile.join("places.sqlite")).filter(|f| f.is_file())
+}
+
+/// (title, url) pairs from Firefox's places.sqlite. Opened read-only and
+/// `immutable` so a running Firefox's lock doesn't get in the way.
+fn firefox_bookmarks(places: &Path) -> Vec<(String, String)> {
+    use rusqlite::{Connection, OpenFlags};
+    let path = places.to_string_lossy()
+        .replace('%', "%25").replace('?', "%3f").replace('#', "%23")
+        .replace('\\', "/");
+    let uri = format!("file:///{path}?immutable=1");
+    let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let Ok(db) = Connection::open_with_flags(uri, flags) else { return Vec::new() };
+    let query = "SELECT b.title, p.url FROM moz_bookmarks b JOIN moz_places p ON p.id = b.fk \
+                 WHERE b.type = 1 AND b.title IS NOT NULL";
+    let Ok(mut stmt) = db.prepare(query) else { return Vec::new() };
+    stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        .unwrap_or_default()
+}
+
+const GAMES_CATEGORY: &str = "Games";
+const STEAM_SCHEME: &str = "steam://";
+// "Steamworks Common Redistributables": installed alongside games, not playable
+const STEAM_REDIST_APPID: &str = "228980";
+
+/// Steam install folder from `HKCU\Software\Valve\Steam\SteamPath`.
+#[cfg(target_os = "windows")]
+fn steam_root() -> Option<PathBuf> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+    let key = RegKey::predef(HKEY_CURRENT_USER).open_subkey(r"Software\Valve\Steam").ok()?;
+    let path: String = key.get_value("SteamPath").ok()?;
+    Some(PathBuf::from(path.replace('/', "\\"))).filter(|p| p.is_dir())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn steam_root() -> Option<PathBuf> { None }
+
+/// Values of `"key" "value"` pairs in a Valve KeyValues (.vdf / .acf) file,
+/// at any nesting level, in file order.
+fn vdf_values(text: &str, key: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| {
+            let mut quoted = line.split('"').skip(1).step_by(2);
+            let k = quoted.next()?;
+            let v = quoted.next()?;
+            k.eq_ignore_ascii_case(key).then(|| v.replace("\\\\", "\\"))
+        })
+        .collect()
+}
+
+/// Installed Steam games from every library in `libraryfolders.vdf`, launched
+/// through `steam://rungameid/<id>`. Icons come from Steam's library cache.
+fn scan_steam_games() -> Vec<AppEntry> {
+    let Some(root) = steam_root() else { return Vec::new() };
+    let mut libraries = vec![root.clone()];
+    if let Ok(text) = std::fs::read_to_string(root.join("steamapps").join("libraryfolders.vdf")) {
+        for lib in vdf_values(&text, "path").into_iter().map(PathBuf::from) {
+            // The main library is listed too, with different slashes / case
+            let known = libraries.iter().any(|l| l.to_string_lossy().eq_ignore_ascii_case(&lib.to_string_lossy()));
+            if !known { libraries.push(lib); }
+        }
+    }
+    let cache = root.join("appcache").join("librarycache");
+
+    let mut games = Vec::new();
+    for lib in libraries {
+        let Ok(entries) = std::fs::read_dir(lib.join("steamapps")) else { continue };
+        for file in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let is_manifest = file.file_name().and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("appmanifest_") && n.ends_with(".acf"));
+            if !is_manifest { continue; }
+            let Ok(text) = std::fs::read_to_string(&file) else { continue };
+            let (Some(id), Some(name)) = (vdf_values(&text, "appid").into_iter().next(),
+                                          vdf_values(&text, "name").into_iter().next()) else { continue };
+            if id == STEAM_REDIST_APPID { continue; }
+            games.push(AppEntry {
+                name,
+                path: format!("{STEAM_SCHEME}rungameid/{id}"),
+                icon: steam_icon(&cache, &id),
+                category: GAMES_CATEGORY.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    games
+}
+
+/// A game's icon from `librarycache` (`<id>_icon.jpg`) as a base64 PNG.
+fn steam_icon(cache: &Path, id: &str) -> Option<String> {
+    let img = image::open(cache.join(format!("{id}_icon.jpg"))).ok()?;
+    encode_png(&fit_icon(img.to_rgba8(), DEFAULT_ICON_SIZE))
+}
+
+const PATH_CATEGORY: &str = "PATH";
+// Files looked at per PATH directory (System32 alone has thousands)
+const PATH_DIR_LIMIT: usize = 500;
+
+/// Command-line tools on PATH: .exe / .bat / .cmd directly inside each PATH
+/// directory, in PATH order so the first hit wins name collisions like the shell does.
+fn scan_path_executables(rules: &ScanRules) -> Vec<AppEntry> {
+    let Some(path) = std::env::var_os("PATH") else { return Vec::new() };
+    let mut apps = Vec::new();
+    for dir in std::env::split_paths(&path) {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for file in entries.filter_map(|e| e.ok()).take(PATH_DIR_LIMIT).map(|e| e.path()) {
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            if !matches!(ext.as_str(), "exe" | "bat" | "cmd") || !file.is_file() { continue; }
+            let name = file.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+            if name.is_empty() || rules.is_junk(&name) { continue; }
+            apps.push(AppEntry {
+                name,
+                path: file.to_string_lossy().to_string(),
+                category: PATH_CATEGORY.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    apps
+}
+
+fn has_scheme(path: &str, scheme: &str) -> bool {
+    path.get(..scheme.len()).is_some_and(|p| p.eq_ignore_ascii_case(scheme))
+}
+
+fn is_shell_uri(path: &str) -> bool {
+    has_scheme(path, "shell:")
+}
+
+const SYSTEM_CATEGORY: &str = "System";
+const SYSTEM_SCHEME: &str = "system:";
+
+// Built-in System entries: (path, zh name, en name)
+const SYSTEM_ENTRIES: &[(&str, &str, &str)] = &[
+    ("ms-settings:display",        "显示设置",     "Display settings"),
+    ("ms-settings:bluetooth",      "蓝牙设置",     "Bluetooth settings"),
+    ("ms-settings:network-wifi",   "WLAN 设置",    "Wi-Fi settings"),
+    ("ms-settings:appsfeatures",   "应用和功能",   "Apps & features"),
+    ("ms-settings:windowsupdate",  "Windows 更新", "Windows Update"),
+    ("system:lock",                "锁定",         "Lock"),
+    ("system:sleep",               "睡眠",         "Sleep"),
+    ("system:signout",             "注销",         "Sign out"),
+    ("system:shutdown",            "关机",         "Shut down"),
+    ("system:restart",             "重启",         "Restart"),
+];
+
+// System actions that end the session; launch_app needs `confirm` for these
+const DESTRUCTIVE_ACTIONS: &[&str] = &["signout", "shutdown", "restart"];
+
+fn is_web_url(path: &str) -> bool {
+    has_scheme(path, "http://") || has_scheme(path, "https://")
+}
+
+const COMMANDS_CATEGORY: &str = "Commands";
+const COMMAND_SCHEME: &str = "command:";
+
+fn command_entries(commands: &[CommandEntry]) -> Vec<AppEntry> {
+    commands.iter()
+        .map(|c| AppEntry {
+            name: c.name.clone(),
+            path: format!("{COMMAND_SCHEME}{}", c.name),
+            category: COMMANDS_CATEGORY.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Bookmarks and Steam games: URLs handed to their registered handler.
+fn is_url_entry(path: &str) -> bool {
+    is_web_url(path) || has_scheme(path, STEAM_SCHEME)
+}
+
+/// Entries that aren't files: Store apps, Settings pages, System actions,
+/// bookmarks, Steam games, saved commands.
+fn is_virtual_entry(path: &str) -> bool {
+    is_shell_uri(path) || has_scheme(path, "ms-settings:") || has_scheme(path, SYSTEM_SCHEME)
+        || is_url_entry(path) || has_scheme(path, COMMAND_SCHEME)
+}
+
+fn system_entries(lang: Lang) -> Vec<AppEntry> {
+    SYSTEM_ENTRIES.iter()
+        .map(|&(path, zh, en)| AppEntry {
+            name: (if lang == Lang::Zh { zh } else { en }).to_string(),
+            path: path.to_string(),
+            category: SYSTEM_CATEGORY.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+// Walked files between index-progress events
+const PROGRESS_EVERY: usize = 200;
+
+/// Rescan and store the result as the current index. Emits `index-progress`
+/// while walking and `index-complete` with the count. Scan roots whose folder
+/// mtimes didn't change reuse their cached walk; PATH, Steam and bookmarks are
+/// always re-read. `force` re-walks every root and re-queries the Store too.
+fn refresh_index(app: &AppHandle, state: &AppState, force: bool) -> Vec<AppEntry> {
+    if force {
+        state.dir_cache.lock().unwrap().clear();
+        *state.store_apps.lock().unwrap() = None;
+    }
+    let (roots, portable_root, rules, threads, lang, skip_slow, commands) = {
+        let cfg = state.config.lock().unwrap();
+        (scan_roots(&cfg), cfg.portableapps_root.clone(), ScanRules::from_config(&cfg),
+            cfg.scan_threads.unwrap_or_else(default_scan_threads), cfg.language, cfg.skip_slow_drives,
+            command_entries(&cfg.commands))
+    };
+    let (apps, skipped, unchanged) = {
+        let mut cache = state.dir_cache.lock().unwrap();
+        let before: HashMap<PathBuf, u64> = cache.iter().map(|(d, c)| (d.clone(), c.hash)).collect();
+        let (apps, skipped) = scan_apps(app, &roots, &rules, threads, skip_slow, &mut cache);
+        let unchanged = roots.iter()
+            .all(|r| before.get(&r.dir).is_some_and(|&h| cache.get(&r.dir).map(|c| c.hash) == Some(h)));
+        (apps, skipped, unchanged)
+    };
+    *state.skipped_dirs.lock().unwrap() = skipped;
+    state.from_cache.store(unchanged, Ordering::SeqCst);
+    // Queried once per session (or forced rescan): Get-StartApps is slow
+    let store = state.store_apps.lock().unwrap()
+        .get_or_insert_with(|| scan_uwp_apps(&rules))
+        .clone();
+    // PATH tools a shortcut already points at, and bookmarks a .url shortcut
+    // already opens, would just be duplicates
+    let known: HashSet<String> = apps.iter()
+        .map(|a| a.target.as_deref().unwrap_or(&a.path).to_lowercase())
+        .collect();
+    let path_tools = scan_path_executables(&rules).into_iter()
+        .filter(|a| !known.contains(&a.path.to_lowercase()));
+    // Shortcuts win name collisions with the portable / Store / PATH copy
+    let portable = portable_root.map(|root| scan_portableapps(Path::new(&root))).unwrap_or_default();
+    let apps = finish_index(apps.into_iter()
+        .chain(portable)
+        .chain(store)
+        .chain(path_tools)
+        .chain(scan_steam_games())
+        .chain(commands)
+        .chain(system_entries(lang))
+        .chain(scan_bookmarks().into_iter().filter(|b| !known.contains(&b.path.to_lowercase())))
+        .collect());
+    let apps: Vec<AppEntry> = {
+        let cfg = state.config.lock().unwrap();
+        apps.into_iter().filter(|a| cfg.allows(&a.path)).collect()
+    };
+    *state.index.lock().unwrap() = apps.clone();
+    *state.scanned_at.lock().unwrap() = Some(now_secs());
+    let _ = app.emit("index-complete", apps.len());
+    apps
+}
+
+// Quiet period after the last shortcut change before apps-changed fires
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Watch the Start Menu / Desktop roots and emit `apps-changed` once shortcuts
+/// stop being added or removed for `WATCH_DEBOUNCE` (installers create several at once).
+fn start_watcher(app: &AppHandle) -> notify::Result<notify::RecommendedWatcher> {
+    use notify::{event::ModifyKind, EventKind, RecursiveMode, Watcher};
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        let relevant = matches!(event.kind,
+                EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_)))
+            && event.paths.iter().any(|p| is_shortcut_file(p));
+        if relevant { let _ = tx.send(()); }
+    })?;
+    for dir in get_start_menu_dirs().iter().filter(|d| d.is_dir()) {
+        watcher.watch(dir, RecursiveMode::Recursive)?;
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            while rx.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+            let _ = app.emit("apps-changed", ());
+        }
+    });
+    Ok(watcher)
+}
+
+/// Latest scan result, scanning now if nothing has been indexed yet.
+fn indexed_apps(app: &AppHandle, state: &AppState) -> Vec<AppEntry> {
+    let cached = state.index.lock().unwrap().clone();
+    if cached.is_empty() { refresh_index(app, state, false) } else { cached }
+}
+
+// ─── Matching ──────────────────────────────────────────────────────────────────
+
+// Same tiers as the frontend scorer in useApps.ts (lower = better)
+const RANK_EXACT: u8     = 0;
+const RANK_PREFIX: u8    = 1;
+const RANK_SUBSTRING: u8 = 2;
+const RANK_INITIALS: u8  = 3;
+
+// resolve_best only auto-picks exact or prefix matches unless told otherwise
+const DEFAULT_RESOLVE_MAX_RANK: u8 = RANK_PREFIX;
+
+/// "Visual Studio Code" → "vsc"
+fn initials(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || matches!(c, '-' | '_' | '.'))
+        .filter_map(|w| w.chars().next())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+// A name spelled out in pinyin, with the source char index of every char
+struct Spelling {
+    text: String,
+    src:  Vec<usize>,
+}
+
+impl Spelling {
+    fn push(&mut self, s: &str, at: usize) {
+        for c in s.chars() {
+            self.text.push(c);
+            self.src.push(at);
+        }
+    }
+}
+
+/// Pinyin spellings of a name with Chinese characters: full syllables and
+/// syllable initials, e.g. "网易云音乐" → ("wangyiyunyinyue", "wyyyy").
+/// Other letters and digits are kept as-is. None if there's nothing to convert.
+fn pinyin_spellings(name: &str) -> Option<(Spelling, Spelling)> {
+    use pinyin::ToPinyin;
+    let mut full = Spelling { text: String::new(), src: Vec::new() };
+    let mut abbr = Spelling { text: String::new(), src: Vec::new() };
+    let mut any = false;
+    for (i, c) in name.chars().enumerate() {
+        if let Some(p) = c.to_pinyin() {
+            full.push(p.plain(), i);
+            abbr.push(p.first_letter(), i);
+            any = true;
+        } else if c.is_alphanumeric() {
+            let lower: String = c.to_lowercase().collect();
+            full.push(&lower, i);
+            abbr.push(&lower, i);
+        }
+    }
+    any.then_some((full, abbr))
+}
+
+fn pinyin_forms(name: &str) -> Option<(String, String)> {
+    pinyin_spellings(name).map(|(full, abbr)| (full.text, abbr.text))
+}
+
+fn literal_rank(name: &str, q: &str) -> Option<u8> {
+    if name == q                { return Some(RANK_EXACT); }
+    if name.starts_with(q)      { return Some(RANK_PREFIX); }
+    if name.contains(q)         { return Some(RANK_SUBSTRING); }
+    let compact: String = q.chars().filter(|c| !c.is_whitespace()).collect();
+    if initials(name).contains(&compact) { return Some(RANK_INITIALS); }
+    None
+}
+
+/// Best tier for `query` against the name itself or, for Chinese names, its
+/// pinyin (typed without spaces) and pinyin initials (`wy` → 网易).
+fn match_rank(name: &str, query: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    let q = query.trim().to_lowercase();
+    if q.is_empty() { return None; }
+    let literal = literal_rank(&name, &q);
+    let phonetic = pinyin_forms(&name).and_then(|(full, abbr)| {
+        let q: String = q.chars().filter(|c| !c.is_whitespace()).collect();
+        if full == q || abbr == q       { Some(RANK_EXACT) }
+        else if full.starts_with(&q)    { Some(RANK_PREFIX) }
+        else if full.contains(&q)       { Some(RANK_SUBSTRING) }
+        else if abbr.contains(&q)       { Some(RANK_INITIALS) }
+        else { None }
+    });
+    literal.into_iter().chain(phonetic).min()
+}
+
+// Fuzzy hits scoring below this are too scattered to be useful
+const MIN_SEARCH_SCORE: i64 = 10;
+// Score of an exact alias match, above any fuzzy score
+const ALIAS_SCORE: i64 = i64::MAX;
+
+/// Apps matching `query` (fuzzy, or an exact alias), best first; ties keep the
+/// index's alphabetical order. search_apps and resolve_best both rank with
+/// this, so the resolved app is always the top search hit.
+fn search_hits(apps: Vec<AppEntry>, query: &str, aliases: &HashMap<String, String>) -> Vec<SearchHit> {
+    let wanted = query.to_lowercase();
+    let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+    let mut hits: Vec<SearchHit> = apps.into_iter()
+        .filter_map(|app| {
+            if aliases.get(&app.path) == Some(&wanted) {
+                return Some(SearchHit { app, score: ALIAS_SCORE, indices: Vec::new() });
+            }
+            let (score, indices) = fuzzy_match(&matcher, &app.name, query)?;
+            Some(SearchHit { app, score, indices })
+        })
+        .collect();
+    // Stable sort keeps the alphabetical order among equal scores
+    hits.sort_by(|a, b| b.score.cmp(&a.score));
+    hits
+}
+
+/// Skim-style fuzzy match of `query` against `name`, falling back to its pinyin
+/// spellings. Indices always point into `name`'s chars.
+fn fuzzy_match(matcher: &fuzzy_matcher::skim::SkimMatcherV2, name: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+    use fuzzy_matcher::FuzzyMatcher;
+    let direct = matcher.fuzzy_indices(name, query);
+    let phonetic = pinyin_spellings(name).and_then(|(full, abbr)| {
+        [full, abbr].into_iter()
+            .filter_map(|sp| {
+                let (score, idx) = matcher.fuzzy_indices(&sp.text, query)?;
+                let mut mapped: Vec<usize> = idx.into_iter().map(|i| sp.src[i]).collect();
+                mapped.dedup();
+                Some((score, mapped))
+            })
+            .max_by_key(|(score, _)| *score)
+    });
+    direct.into_iter().chain(phonetic)
+        .max_by_key(|(score, _)| *score)
+        .filter(|(score, _)| *score >= MIN_SEARCH_SCORE)
+}
+
+// A launch's weight halves every this many days
+const FRECENCY_HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Recency-weighted launch count: every launch counts, recent ones count more.
+fn frecency(stats: &LaunchStats, now: u64) -> f64 {
+    let age_days = now.saturating_sub(stats.last) as f64 / 86_400.0;
+    stats.count as f64 * 0.5f64.powf(age_days / FRECENCY_HALF_LIFE_DAYS)
+}
+
+// ─── Calculator ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CalcToken { Num(f64), Op(char), Open, Close }
+
+// 'u' is unary minus
+fn precedence(op: char) -> u8 {
+    match op {
+        '+' | '-' => 1,
+        '*' | '/' | '%' => 2,
+        _ => 3,
+    }
+}
+
+/// None if `query` contains anything besides numbers, + - * / %, parentheses and spaces.
+fn calc_tokens(query: &str) -> Option<Vec<CalcToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() { continue; }
+        if c.is_ascii_digit() || c == '.' {
+            let mut num = c.to_string();
+            while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit() || **d == '.') {
+                num.push(d);
+                chars.next();
+            }
+            tokens.push(CalcToken::Num(num.parse().ok()?));
+            continue;
+        }
+        let unary = matches!(tokens.last(), None | Some(CalcToken::Op(_)) | Some(CalcToken::Open));
+        match c {
+            '+' if unary => {}
+            '-' if unary => tokens.push(CalcToken::Op('u')),
+            '+' | '-' | '*' | '/' | '%' => tokens.push(CalcToken::Op(c)),
+            '(' => tokens.push(CalcToken::Open),
+            ')' => tokens.push(CalcToken::Close),
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+/// Shunting-yard: infix tokens to postfix. None on unbalanced parentheses.
+fn to_postfix(tokens: Vec<CalcToken>) -> Option<Vec<CalcToken>> {
+    let mut out = Vec::new();
+    let mut ops: Vec<CalcToken> = Vec::new();
+    for t in tokens {
+        match t {
+            CalcToken::Num(_) => out.push(t),
+            CalcToken::Op(op) => {
+                // Binary operators are left-associative, unary minus right-associative
+                while let Some(&CalcToken::Op(top)) = ops.last() {
+                    let (p, q) = (precedence(top), precedence(op));
+                    if p > q || (p == q && op != 'u') { out.push(ops.pop()?); } else { break; }
+                }
+                ops.push(t);
+            }
+            CalcToken::Open => ops.push(t),
+            CalcToken::Close => loop {
+                match ops.pop()? {
+                    CalcToken::Open => break,
+                    op => out.push(op),
+                }
+            },
+        }
+    }
+    while let Some(op) = ops.pop() {
+        if op == CalcToken::Open { return None; }
+        out.push(op);
+    }
+    Some(out)
+}
+
+/// Evaluate an arithmetic query. Ok(None) if it isn't one (plain text, a bare
+/// number, or incomplete input like `3+`); Err for division by zero.
+fn evaluate(query: &str) -> Result<Option<f64>, String> {
+    let Some(tokens) = calc_tokens(query) else { return Ok(None) };
+    if !tokens.iter().any(|t| matches!(t, CalcToken::Op(op) if *op != 'u')) { return Ok(None); }
+    let Some(postfix) = to_postfix(tokens) else { return Ok(None) };
+
+    let mut stack: Vec<f64> = Vec::new();
+    for t in postfix {
+        match t {
+            CalcToken::Num(n) => stack.push(n),
+            CalcToken::Op('u') => {
+                let Some(a) = stack.pop() else { return Ok(None) };
+                stack.push(-a);
+            }
+            CalcToken::Op(op) => {
+                let (Some(b), Some(a)) = (stack.pop(), stack.pop()) else { return Ok(None) };
+                if matches!(op, '/' | '%') && b == 0.0 { return Err("division by zero".into()); }
+                stack.push(match op {
+                    '+' => a + b,
+                    '-' => a - b,
+                    '*' => a * b,
+                    '/' => a / b,
+                    _ => a % b,
+                });
+            }
+            _ => return Ok(None),
+        }
+    }
+    match stack[..] {
+        [v] if v.is_finite() => Ok(Some(v)),
+        [_] => Err("result out of range".into()),
+        _ => Ok(None),
+    }
+}
+
+/// Whole numbers without a decimal point, others to at most 10 places.
+fn format_number(v: f64) -> String {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        return format!("{}", v as i64);
+    }
+    let s = format!("{v:.10}");
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// ─── Shortcut Resolution ───────────────────────────────────────────────────────
+
+// Shortcuts handed to one resolver thread, and how many resolvers may run at once
+const RESOLVE_CHUNK: usize = 64;
+const MAX_RESOLVERS: usize = 2;
+
+fn is_lnk(path: &str) -> bool {
+    path.to_lowercase().ends_with(".lnk")
+}
+
+// What a .lnk file stores besides its icon
+#[derive(Debug, Clone, Default)]
+struct ShortcutInfo {
+    target: Option<String>,
+    args: Option<String>,
+    working_dir: Option<String>,
+}
+
+/// Read a .lnk through the shell's IShellLink (no PowerShell). None if the file
+/// can't be loaded; fields the shortcut doesn't set (e.g. an advertised MSI
+/// shortcut's target) are None.
+#[cfg(target_os = "windows")]
+fn read_shortcut(path: &str) -> Option<ShortcutInfo> {
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, IPersistFile, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, STGM_READ,
+    };
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+    fn text(buf: &[u16]) -> Option<String> {
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        Some(String::from_utf16_lossy(&buf[..len])).filter(|s| !s.trim().is_empty())
+    }
+    unsafe {
+        // No-op (or a mode-mismatch error we can ignore) if this thread already joined an apartment
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER).ok()?;
+        link.cast::<IPersistFile>().ok()?.Load(&HSTRING::from(path), STGM_READ).ok()?;
+
+        // Longer than MAX_PATH: arguments can be
+        let (mut target_buf, mut args_buf, mut dir_buf) = ([0u16; 1024], [0u16; 1024], [0u16; 1024]);
+        let target = link.GetPath(&mut target_buf, std::ptr::null_mut(), 0).ok().and_then(|_| text(&target_buf));
+        let args = link.GetArguments(&mut args_buf).ok().and_then(|_| text(&args_buf));
+        let working_dir = link.GetWorkingDirectory(&mut dir_buf).ok().and_then(|_| text(&dir_buf));
+        Some(ShortcutInfo { target, args, working_dir })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_shortcut(_: &str) -> Option<ShortcutInfo> { None }
+
+fn resolve_lnk_targets(paths: &[String]) -> Vec<Option<String>> {
+    paths.iter().map(|p| read_shortcut(p).and_then(|l| l.target)).collect()
+}
+
+/// Resolve entry paths to launch targets, filling the shared cache for any
+/// shortcut not seen before. Non-shortcut paths (exe, portable apps) are their own target.
+fn resolve_targets_cached(state: &AppState, paths: &[String]) -> Vec<Option<String>> {
+    let mut missing: Vec<String> = {
+        let cache = state.targets.lock().unwrap();
+        paths.iter().filter(|p| is_lnk(p) && !cache.contains_key(*p)).cloned().collect()
+    };
+    missing.sort();
+    missing.dedup();
+
+    let chunks: Vec<&[String]> = missing.chunks(RESOLVE_CHUNK).collect();
+    for group in chunks.chunks(MAX_RESOLVERS) {
+        let results: Vec<Vec<Option<String>>> = std::thread::scope(|s| {
+            let handles: Vec<_> = group.iter().map(|c| s.spawn(move || resolve_lnk_targets(c))).collect();
+            handles.into_iter().map(|h| h.join().unwrap_or_default()).collect()
+        });
+        let mut cache = state.targets.lock().unwrap();
+        for (chunk, resolved) in group.iter().zip(results) {
+            for (path, target) in chunk.iter().zip(resolved) {
+                cache.insert(path.clone(), target);
+            }
+        }
+    }
+
+    let cache = state.targets.lock().unwrap();
+    paths.iter()
+        .map(|p| if is_lnk(p) { cache.get(p).cloned().flatten() } else { Some(p.clone()) })
+        .collect()
+}
+
+// ─── Registry ──────────────────────────────────────────────────────────────────
+
+// One installed program from the Uninstall keys
+#[derive(Debug, Clone, Default)]
+struct UninstallEntry {
+    display_name: String,
+    install_location: Option<String>,
+    url_info_about: Option<String>,
+    help_link: Option<String>,
+    url_update_info: Option<String>,
+}
+
+/// Programs listed under HKCU/HKLM `...\CurrentVersion\Uninstall` (including
+/// the 32-bit WOW6432Node view). Keys we can't read are skipped.
+#[cfg(target_os = "windows")]
+fn uninstall_entries() -> Vec<UninstallEntry> {
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use winreg::RegKey;
+    const KEYS: &[&str] = &[
+        r"Software\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"Software\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+    ];
+
+    let mut out = Vec::new();
+    for hive in [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE] {
+        for path in KEYS {
+            let Ok(root) = RegKey::predef(hive).open_subkey(path) else { continue };
+            for name in root.enum_keys().filter_map(|k| k.ok()) {
+                let Ok(key) = root.open_subkey(&name) else { continue };
+                let Ok(display_name) = key.get_value::<String, _>("DisplayName") else { continue };
+                let get = |v: &str| key.get_value::<String, _>(v).ok().filter(|s| !s.trim().is_empty());
+                out.push(UninstallEntry {
+                    display_name,
+                    install_location: get("InstallLocation"),
+                    url_info_about: get("URLInfoAbout"),
+                    help_link: get("HelpLink"),
+                    url_update_info: get("URLUpdateInfo"),
+                });
+            }
+        }
+    }
+    out
+}
+
+#[cfg(not(target_os = "windows"))]
+fn uninstall_entries() -> Vec<UninstallEntry> { Vec::new() }
+
+/// Uninstall entry that best matches an app: its InstallLocation contains the
+/// target (strongest), else the display name equals or contains the app name.
+fn find_uninstall_entry(name: &str, target: Option<&str>) -> Option<UninstallEntry> {
+    let key = squash(name);
+    let target = target.map(|t| t.to_lowercase());
+    uninstall_entries().into_iter()
+        .filter_map(|e| {
+            let in_location = match (&e.install_location, &target) {
+                (Some(loc), Some(t)) => {
+                    let loc = loc.trim_end_matches('\\').to_lowercase();
+                    !loc.is_empty() && t.starts_with(&loc)
+                }
+                _ => false,
+            };
+            let display = squash(&e.display_name);
+            let score = if in_location { 0 }
+                else if display == key { 1 }
+                else if key.len() >= 3 && (display.contains(&key) || key.contains(&display)) { 2 }
+                else { return None };
+            Some((score, e))
+        })
+        .min_by_key(|(score, _)| *score)
+        .map(|(_, e)| e)
+}
+
+// ─── Icon Extraction ───────────────────────────────────────────────────────────
+
+// Icon edge length in px when the frontend doesn't ask for one, and the accepted range
+const DEFAULT_ICON_SIZE: u32 = 48;
+const MAX_ICON_SIZE: u32 = 256;
+
+/// Extract the icon from a .lnk file (or its target exe) as a `size`×`size` base64 PNG.
+/// Tries the native shell API first and PowerShell only if that fails.
+/// Returns None on any failure so the frontend can fall back to the color avatar.
+fn extract_icon_base64(path: &str, size: u32) -> Option<String> {
+    // Bookmarks and Steam games have no file to take an icon from
+    if is_url_entry(path) { return None; }
+    extract_icon_native(path, size)
+        .or_else(|| extract_icon_powershell(path).map(|png| scale_png(png, size)))
+}
+
+/// Resample to exactly `size`×`size` (no-op if it already is).
+fn fit_icon(img: image::RgbaImage, size: u32) -> image::RgbaImage {
+    if img.width() == size && img.height() == size { return img; }
+    image::imageops::resize(&img, size, size, image::imageops::FilterType::Lanczos3)
+}
+
+fn encode_png(img: &image::RgbaImage) -> Option<String> {
+    use base64::Engine;
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).ok()?;
+    Some(base64::engine::general_purpose::STANDARD.encode(png))
+}
+
+/// `fit_icon` for a base64 PNG; returns the input unchanged if it can't be decoded.
+fn scale_png(png_b64: String, size: u32) -> String {
+    use base64::Engine;
+    let decoded = base64::engine::general_purpose::STANDARD.decode(&png_b64).ok()
+        .and_then(|bytes| image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).ok());
+    match decoded {
+        Some(img) if img.width() != size || img.height() != size => {
+            encode_png(&fit_icon(img.to_rgba8(), size)).unwrap_or(png_b64)
+        }
+        _ => png_b64,
+    }
+}
+
+/// Icon via the shell's system image lists + GDI, no child process. Works
+/// where PowerShell is disabled and is much faster. Picks the smallest list
+/// at least `size` px (16/32/48/256) and scales to fit. None for anything it
+/// can't read (e.g. a monochrome icon without a color bitmap).
+#[cfg(target_os = "windows")]
+fn extract_icon_native(path: &str, size: u32) -> Option<String> {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
+    use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+    use windows::Win32::UI::Shell::{
+        SHGetFileInfoW, SHFILEINFOW, SHGFI_SYSICONINDEX, SHIL_EXTRALARGE, SHIL_JUMBO, SHIL_LARGE, SHIL_SMALL,
+    };
+
+    let (w, h, pixels) = unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let mut info = SHFILEINFOW::default();
+        let found = SHGetFileInfoW(&HSTRING::from(path), FILE_FLAGS_AND_ATTRIBUTES(0), Some(&mut info),
+            std::mem::size_of::<SHFILEINFOW>() as u32, SHGFI_SYSICONINDEX);
+        if found == 0 { return None; }
+
+        let list = match size {
+            0..=16  => SHIL_SMALL,
+            17..=32 => SHIL_LARGE,
+            33..=48 => SHIL_EXTRALARGE,
+            _       => SHIL_JUMBO,
+        };
+        let icon = image_list_icon(list, info.iIcon)?;
+        // Apps without a 256px icon come back as a 48px one in the jumbo
+        // list's top-left corner; use the 48px list instead of upscaling that
+        if list == SHIL_JUMBO && only_top_left(&icon, 48) {
+            image_list_icon(SHIL_EXTRALARGE, info.iIcon)?
+        } else {
+            icon
+        }
+    };
+
+    let img = image::RgbaImage::from_raw(w, h, pixels)?;
+    encode_png(&fit_icon(img, size))
+}
+
+/// Icon `index` from one of the system image lists (SHIL_*) as RGBA.
+#[cfg(target_os = "windows")]
+unsafe fn image_list_icon(list: u32, index: i32) -> Option<(u32, u32, Vec<u8>)> {
+    use windows::Win32::Graphics::Gdi::{DeleteObject, HGDIOBJ};
+    use windows::Win32::UI::Controls::{IImageList, ILD_TRANSPARENT};
+    use windows::Win32::UI::Shell::SHGetImageList;
+    use windows::Win32::UI::WindowsAndMessaging::{DestroyIcon, GetIconInfo, ICONINFO};
+
+    let images: IImageList = SHGetImageList(list as i32).ok()?;
+    let hicon = images.GetIcon(index, ILD_TRANSPARENT.0).ok()?;
+    let mut icon = ICONINFO::default();
+    let got = GetIconInfo(hicon, &mut icon);
+    let _ = DestroyIcon(hicon);
+    got.ok()?;
+    let pixels = bitmap_rgba(icon.hbmColor);
+    let _ = DeleteObject(HGDIOBJ(icon.hbmColor.0));
+    let _ = DeleteObject(HGDIOBJ(icon.hbmMask.0));
+    pixels
+}
+
+/// True if every pixel outside the top-left `n`×`n` square is transparent.
+#[cfg(target_os = "windows")]
+fn only_top_left((w, _, pixels): &(u32, u32, Vec<u8>), n: u32) -> bool {
+    pixels.chunks_exact(4).enumerate().all(|(i, px)| {
+        let (x, y) = (i as u32 % w, i as u32 / w);
+        (x < n && y < n) || px[3] == 0
+    })
+}
+
+/// Read a color bitmap as (width, height, RGBA bytes).
+#[cfg(target_os = "windows")]
+unsafe fn bitmap_rgba(bitmap: windows::Win32::Graphics::Gdi::HBITMAP) -> Option<(u32, u32, Vec<u8>)> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Graphics::Gdi::{
+        GetDC, GetDIBits, GetObjectW, ReleaseDC, BITMAP, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HGDIOBJ,
+    };
+
+    let mut bm = BITMAP::default();
+    let size = std::mem::size_of::<BITMAP>() as i32;
+    if GetObjectW(HGDIOBJ(bitmap.0), size, Some(&mut bm as *mut BITMAP as *mut _)) == 0 { return None; }
+    let (w, h) = (bm.bmWidth, bm.bmHeight);
+    if w <= 0 || h <= 0 { return None; }
+
+    let mut bmi = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: w,
+            biHeight: -h,   // top-down rows
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut buf = vec![0u8; (w * h * 4) as usize];
+    let hdc = GetDC(HWND::default());
+    let lines = GetDIBits(hdc, bitmap, 0, h as u32, Some(buf.as_mut_ptr() as *mut _), &mut bmi, DIB_RGB_COLORS);
+    ReleaseDC(HWND::default(), hdc);
+    if lines == 0 { return None; }
+
+    // BGRA → RGBA. Old icons without an alpha channel read back fully transparent.
+    let has_alpha = buf.chunks_exact(4).any(|p| p[3] != 0);
+    for px in buf.chunks_exact_mut(4) {
+        px.swap(0, 2);
+        if !has_alpha { px[3] = 255; }
+    }
+    Some((w as u32, h as u32, buf))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract_icon_native(_: &str, _: u32) -> Option<String> { None }
+
+// PowerShell icon extractors allowed to run at once, and how long one may take
+// before it's killed (a batch gets a little extra per path)
+const MAX_POWERSHELL_JOBS: usize = 4;
+const POWERSHELL_TIMEOUT: Duration = Duration::from_secs(3);
+const POWERSHELL_TIMEOUT_PER_PATH: Duration = Duration::from_millis(250);
+
+/// Counting semaphore: at most `max` holders of a `JobSlot` at once.
+#[cfg(any(target_os = "windows", test))]
+struct JobSlots {
+    running: Mutex<usize>,
+    freed: std::sync::Condvar,
+    max: usize,
+}
+
+/// One of a `JobSlots`' slots, released on drop.
+#[cfg(any(target_os = "windows", test))]
+struct JobSlot<'a>(&'a JobSlots);
+
+#[cfg(any(target_os = "windows", test))]
+impl JobSlots {
+    const fn new(max: usize) -> Self {
+        JobSlots { running: Mutex::new(0), freed: std::sync::Condvar::new(), max }
+    }
+
+    /// Block until a slot is free, then take it.
+    fn acquire(&self) -> JobSlot<'_> {
+        let mut n = self.freed.wait_while(self.running.lock().unwrap(), |n| *n >= self.max).unwrap();
+        *n += 1;
+        JobSlot(self)
+    }
+}
+
+#[cfg(any(target_os = "windows", test))]
+impl Drop for JobSlot<'_> {
+    fn drop(&mut self) {
+        *self.0.running.lock().unwrap() -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+// Running PowerShell extractors
+#[cfg(any(target_os = "windows", test))]
+static POWERSHELL_JOBS: JobSlots = JobSlots::new(MAX_POWERSHELL_JOBS);
+
+/// Run a PowerShell script once a job slot is free. Returns (succeeded, stdout),
+/// or None if it couldn't start or was killed after `timeout`.
+#[cfg(target_os = "windows")]
+fn run_powershell(script: &str, timeout: Duration) -> Option<(bool, String)> {
+    let _slot = POWERSHELL_JOBS.acquire();
+    let mut cmd = std::process::Command::new("powershell");
+    cmd.args(["-NoProfile", "-NonInteractive", "-Command", script]);
+    run_with_timeout(cmd, timeout)
+}
+
+/// Spawn `cmd` and collect its stdout. Returns (succeeded, stdout), or None if
+/// it couldn't start or was killed after `timeout`.
+#[cfg(any(target_os = "windows", test))]
+fn run_with_timeout(mut cmd: std::process::Command, timeout: Duration) -> Option<(bool, String)> {
+    use std::io::Read;
+    let mut child = cmd
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .ok()?;
+    // Drain stdout alongside, or a large batch fills the pipe and never exits
+    let mut stdout = child.stdout.take()?;
+    let reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(25)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+    let out = reader.join().ok()?;
+    Some((status.success(), String::from_utf8_lossy(&out).into_owned()))
+}
+
+/// Icon via PowerShell + System.Drawing — available on all Windows editions.
+#[cfg(target_os = "windows")]
+fn extract_icon_powershell(lnk_path: &str) -> Option<String> {
+    // PowerShell script: resolve .lnk target → extract icon → return base64 PNG
+    let script = format!(
+        r#"
+$ErrorActionPreference = 'Stop'
+try {{
+    Add-Type -Assembly System.Drawing
+    $shell = New-Object -ComObject WScript.Shell
+    $lnk = $shell.CreateShortcut('{lnk}')
+    $target = $lnk.TargetPath
+    if (-not $target -or -not (Test-Path $target)) {{ $target = '{lnk}' }}
+    $icon = [System.Drawing.Icon]::ExtractAssociatedIcon($target)
+    if (-not $icon) {{ exit 1 }}
+    $bmp = $icon.ToBitmap()
+    $ms = New-Object System.IO.MemoryStream
+    $bmp.Save($ms, [System.Drawing.Imaging.ImageFormat]::Png)
+    [Convert]::ToBase64String($ms.ToArray())
+}} catch {{ exit 1 }}
+"#,
+        lnk = lnk_path.replace("'", "''")
+    );
+
+    let Some((success, out)) = run_powershell(&script, POWERSHELL_TIMEOUT) else {
+        eprintln!("[QuickLaunch] Icon extraction timed out: {lnk_path}");
+        return None;
+    };
+    if !success { return None; }
+    let b64 = out.trim().to_string();
+    if b64.is_empty() { return None; }
+    Some(b64)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract_icon_powershell(_: &str) -> Option<String> { None }
+
+// Paths handed to one batched icon extractor (keeps the command line well under its limit)
+const ICON_CHUNK: usize = 64;
+
+/// `extract_icon_powershell` for many paths in a single PowerShell run.
+/// Output is one line per input (empty = no icon), so results stay aligned.
+#[cfg(target_os = "windows")]
+fn extract_icons_powershell(paths: &[String]) -> Vec<Option<String>> {
+    let list = paths.iter()
+        .map(|p| format!("'{}'", p.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(",");
+    let script = format!(
+        r#"
+Add-Type -Assembly System.Drawing
+$shell = New-Object -ComObject WScript.Shell
+foreach ($p in @({list})) {{
+    $b64 = ''
+    try {{
+        $target = $shell.CreateShortcut($p).TargetPath
+        if (-not $target -or -not (Test-Path $target)) {{ $target = $p }}
+        $icon = [System.Drawing.Icon]::ExtractAssociatedIcon($target)
+        if ($icon) {{
+            $ms = New-Object System.IO.MemoryStream
+            $icon.ToBitmap().Save($ms, [System.Drawing.Imaging.ImageFormat]::Png)
+            $b64 = [Convert]::ToBase64String($ms.ToArray())
+        }}
+    }} catch {{ }}
+    Write-Output $b64
+}}
+"#
+    );
+
+    let timeout = POWERSHELL_TIMEOUT + POWERSHELL_TIMEOUT_PER_PATH * paths.len() as u32;
+    let Some((_, text)) = run_powershell(&script, timeout) else {
+        eprintln!("[QuickLaunch] Icon extraction timed out for {} paths: {}", paths.len(), paths.join(", "));
+        return vec![None; paths.len()];
+    };
+    let mut lines = text.lines().map(|l| Some(l.trim().to_string()).filter(|t| !t.is_empty()));
+    paths.iter().map(|_| lines.next().flatten()).collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract_icons_powershell(paths: &[String]) -> Vec<Option<String>> { vec![None; paths.len()] }
+
+fn count_icon(counts: &IconCounters, icon: &Option<String>) {
+    counts.total.fetch_add(1, Ordering::Relaxed);
+    let outcome = if icon.is_some() { &counts.extracted } else { &counts.failed };
+    outcome.fetch_add(1, Ordering::Relaxed);
+}
+
+/// `extract_icon_base64`, counted into the shared icon stats.
+fn extract_icon_counted(counts: &IconCounters, path: &str, size: u32) -> Option<String> {
+    let icon = extract_icon_base64(path, size);
+    count_icon(counts, &icon);
+    icon
+}
+
+fn icon_stats(c: &IconCounters) -> IconStats {
+    IconStats {
+        total: c.total.load(Ordering::Relaxed),
+        cached: c.cached.load(Ordering::Relaxed),
+        extracted: c.extracted.load(Ordering::Relaxed),
+        failed: c.failed.load(Ordering::Relaxed),
+    }
+}
+
+fn icon_cache_dir<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path().app_cache_dir().ok().map(|d| d.join("icons"))
+}
+
+/// Cache file for an app's icon at `size` px: keyed by its resolved target (the
+/// entry path itself if unresolvable) plus that file's mtime, so an updated app
+/// gets a new key and its icon is re-extracted. None if the source can't be stat'ed.
+fn icon_cache_file(dir: &Path, path: &str, target: Option<String>, size: u32) -> Option<PathBuf> {
+    use std::hash::{Hash, Hasher};
+    let source = target.filter(|t| Path::new(t).exists()).unwrap_or_else(|| path.to_string());
+    let mtime = std::fs::metadata(&source).and_then(|m| m.modified()).ok()?;
+    let mut h = std::collections::hash_map::DefaultHasher::new();
+    source.to_lowercase().hash(&mut h);
+    mtime.hash(&mut h);
+    Some(dir.join(format!("{:016x}-{size}.png", h.finish())))
+}
+
+/// A cached icon as base64 PNG, counted as a cache hit.
+fn read_cached_icon(counts: &IconCounters, file: &Path) -> Option<String> {
+    use base64::Engine;
+    let bytes = std::fs::read(file).ok()?;
+    counts.total.fetch_add(1, Ordering::Relaxed);
+    counts.cached.fetch_add(1, Ordering::Relaxed);
+    Some(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+fn write_cached_icon(file: &Path, png_b64: &str) {
+    use base64::Engine;
+    let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(png_b64) else { return };
+    if let Some(dir) = file.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = std::fs::write(file, bytes);
+}
+
+const CUSTOM_ICON_DIR: &str = "custom_icons";
+
+/// Where a user-assigned icon for `path` lives (config dir, keyed by path).
+fn custom_icon_file<R: Runtime>(app: &AppHandle<R>, path: &str) -> Option<PathBuf> {
+    use std::hash::{Hash, Hasher};
+    let mut h = std::collections::hash_map::DefaultHasher::new();
+    path.to_lowercase().hash(&mut h);
+    config_file(app, CUSTOM_ICON_DIR).map(|d| d.join(format!("{:016x}.png", h.finish())))
+}
+
+/// The user's icon for `path` at `size` px, if one was set.
+fn read_custom_icon<R: Runtime>(app: &AppHandle<R>, path: &str, size: u32) -> Option<String> {
+    let bytes = std::fs::read(custom_icon_file(app, path)?).ok()?;
+    let img = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).ok()?;
+    encode_png(&fit_icon(img.to_rgba8(), size))
+}
+
+/// (ProductVersion or FileVersion, CompanyName) from an exe/dll's version
+/// resource. None if the file has no version resource.
+#[cfg(target_os = "windows")]
+fn file_version_info(file: &str) -> Option<(Option<String>, Option<String>)> {
+    use std::ffi::c_void;
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::{GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW};
+    let name = HSTRING::from(file);
+    let size = unsafe { GetFileVersionInfoSizeW(&name, None) };
+    if size == 0 { return None; }
+    let mut data = vec![0u8; size as usize];
+    unsafe { GetFileVersionInfoW(&name, 0, size, data.as_mut_ptr().cast()) }.ok()?;
+
+    let query = |sub: String| -> Option<(*const c_void, u32)> {
+        let (mut ptr, mut len) = (std::ptr::null_mut::<c_void>(), 0u32);
+        let found = unsafe { VerQueryValueW(data.as_ptr().cast(), &HSTRING::from(sub), &mut ptr, &mut len) };
+        (found.as_bool() && !ptr.is_null() && len > 0).then_some((ptr as *const c_void, len))
+    };
+    // Declared (language, code page) pairs, then the usual US-English ones
+    let mut langs: Vec<(u16, u16)> = query(r"\VarFileInfo\Translation".into())
+        .map(|(p, len)| unsafe { std::slice::from_raw_parts(p as *const u16, len as usize / 2) }
+            .chunks_exact(2).map(|c| (c[0], c[1])).collect())
+        .unwrap_or_default();
+    langs.extend([(0x0409, 0x04b0), (0x0409, 0x04e4)]);
+    let string = |key: &str| -> Option<String> {
+        langs.iter().find_map(|(lang, cp)| {
+            let (p, len) = query(format!(r"\StringFileInfo\{lang:04x}{cp:04x}\{key}"))?;
+            // `len` counts UTF-16 chars including the terminating NUL
+            let chars = unsafe { std::slice::from_raw_parts(p as *const u16, len as usize) };
+            let text = String::from_utf16_lossy(chars).trim_end_matches('\0').trim().to_string();
+            (!text.is_empty()).then_some(text)
+        })
+    };
+    Some((string("ProductVersion").or_else(|| string("FileVersion")), string("CompanyName")))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn file_version_info(_file: &str) -> Option<(Option<String>, Option<String>)> { None }
+
+// Encoding the frontend asked for in get_icon; PNG unless it says it can take WebP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconFormat { #[default] Png, Webp }
+
+/// Re-encode a base64 PNG icon as lossless WebP (noticeably smaller for
+/// large icons). Returns None if decoding/encoding fails so callers keep the PNG.
+fn png_to_webp(png_b64: &str) -> Option<String> {
+    use base64::Engine;
+    let engine = base64::engine::general_purpose::STANDARD;
+    let bytes = engine.decode(png_b64).ok()?;
+    let img = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).ok()?;
+    let mut webp = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut webp), image::ImageFormat::WebP).ok()?;
+    Some(engine.encode(webp))
+}
+
+/// Alpha-weighted average color of a base64 PNG, as "#rrggbb".
+/// Near-transparent pixels are skipped so the icon's padding doesn't wash it out.
+fn accent_color(png_b64: &str) -> Option<String> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD.decode(png_b64).ok()?;
+    let img = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).ok()?.to_rgba8();
+
+    let (mut r, mut g, mut b, mut w) = (0u64, 0u64, 0u64, 0u64);
+    for px in img.pixels() {
+        let [pr, pg, pb, pa] = px.0;
+        if pa < 16 { continue; }
+        let a = pa as u64;
+        r += pr as u64 * a;
+        g += pg as u64 * a;
+        b += pb as u64 * a;
+        w += a;
+    }
+    if w == 0 { return None; }
+    Some(format!("#{:02x}{:02x}{:02x}", r / w, g / w, b / w))
+}
+
+// ─── Tauri Commands ────────────────────────────────────────────────────────────
+
+/// Rescan, persist the result to apps.json and record first-seen times.
+fn rescan_and_save(app: &AppHandle, state: &AppState, force: bool) -> Vec<AppEntry> {
+    let apps = refresh_index(app, state, force);
+    save_app_cache(app, state);
+    record_first_seen(app, &apps);
+    apps
+}
+
+/// The index. Served straight from the cache when there is one, with a
+/// background rescan that emits `apps-changed` if the result differs;
+/// `force` does a full rescan before returning. `exclude_broken` leaves out shortcuts
+/// whose target is gone (they're listed with `broken: true` otherwise).
+#[tauri::command]
+async fn get_apps(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    force: Option<bool>,
+    exclude_broken: Option<bool>,
+) -> Result<Vec<AppEntry>, String> {
+    let cached = state.index.lock().unwrap().clone();
+    let force = force.unwrap_or(false);
+    let mut apps = if force || cached.is_empty() {
+        rescan_and_save(&app, &state, force)
+    } else {
+        if !state.rescanning.swap(true, Ordering::SeqCst) {
+            let app = app.clone();
+            let before = cached.clone();
+            std::thread::spawn(move || {
+                let state = app.state::<AppState>();
+                let fresh = rescan_and_save(&app, &state, false);
+                state.rescanning.store(false, Ordering::SeqCst);
+                if fresh != before { let _ = app.emit("apps-changed", ()); }
+            });
+        }
+        // Targets may have gone since the index was built
+        let mut apps = cached;
+        refresh_broken(&mut apps);
+        apps
+    };
+    if exclude_broken.unwrap_or(false) {
+        apps.retain(|a| !a.broken);
+    }
+    mark_pinned(&mut apps, &state.config.lock().unwrap().pins);
+    Ok(apps)
+}
+
+fn mark_pinned(apps: &mut [AppEntry], pins: &[String]) {
+    for a in apps {
+        a.pinned = pins.contains(&a.path);
+    }
+}
+
+/// Indexed apps with their launch history: pinned apps first in their saved
+/// order, then most-used first (frecency). Never-launched apps and ties keep
+/// alphabetical order. Hidden categories are left out.
+#[tauri::command]
+async fn get_apps_ranked(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<Vec<RankedApp>, String> {
+    Ok(frecency_order(without_hidden_categories(indexed_apps(&app, &state), &state), &state))
+}
+
+/// The default browse list: drops apps in `hidden_categories` unless pinned.
+/// Search still finds them.
+fn without_hidden_categories(mut apps: Vec<AppEntry>, state: &AppState) -> Vec<AppEntry> {
+    let cfg = state.config.lock().unwrap();
+    apps.retain(|a| !cfg.hidden_categories.contains(&a.category) || cfg.pins.contains(&a.path));
+    apps
+}
+
+/// Apps with their launch history: pins in saved order, then most-used first;
+/// never-launched apps follow alphabetically.
+fn frecency_order(mut apps: Vec<AppEntry>, state: &AppState) -> Vec<RankedApp> {
+    let usage = state.usage.lock().unwrap().clone();
+    let pins = state.config.lock().unwrap().pins.clone();
+    mark_pinned(&mut apps, &pins);
+    let pin_pos = |a: &AppEntry| pins.iter().position(|p| *p == a.path).unwrap_or(usize::MAX);
+    let now = now_secs();
+    let mut ranked: Vec<(f64, RankedApp)> = apps.into_iter()
+        .map(|app| {
+            let stats = usage.get(&app.path);
+            let score = stats.map_or(0.0, |s| frecency(s, now));
+            (score, RankedApp {
+                launch_count: stats.map_or(0, |s| s.count),
+                last_launched: stats.map(|s| s.last),
+                app,
+            })
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        pin_pos(&a.1.app).cmp(&pin_pos(&b.1.app))
+            .then_with(|| b.0.total_cmp(&a.0))
+            .then_with(|| a.1.app.name.cmp(&b.1.app.name))
+    });
+    ranked.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Whether a recent-list path can still be launched: the file (and a
+/// shortcut's target) exists. Store apps, Settings pages and System entries always can.
+fn still_launchable(path: &str, index: &[AppEntry]) -> bool {
+    if is_virtual_entry(path) { return true; }
+    if !Path::new(path).exists() { return false; }
+    !index.iter().any(|a| a.path == path && a.broken)
+}
+
+/// The last `limit` (default all) launched apps, most recent first.
+/// Entries whose file or shortcut target is gone are dropped from the list.
+#[tauri::command]
+async fn get_recent(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<AppEntry>, String> {
+    let apps = indexed_apps(&app, &state);
+    let recent = state.config.lock().unwrap().recent.clone();
+    let (live, gone): (Vec<String>, Vec<String>) = recent.into_iter()
+        .partition(|p| still_launchable(p, &apps));
+    if !gone.is_empty() {
+        let config = {
+            let mut cfg = state.config.lock().unwrap();
+            cfg.recent.retain(|p| !gone.contains(p));
+            cfg.clone()
+        };
+        save_config(&app, &config)?;
+    }
+    Ok(live.into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|path| apps.iter().find(|a| a.path == path).cloned().unwrap_or_else(|| AppEntry {
+            name: Path::new(&path).file_stem().and_then(|s| s.to_str()).unwrap_or(&path).to_string(),
+            path,
+            category: "Other".to_string(),
+            ..Default::default()
+        }))
+        .collect())
+}
+
+/// Drop the per-directory cache and walk every scan root from scratch.
+/// `skipped` lists roots that timed out or were on a skipped drive type.
+#[tauri::command]
+async fn force_full_scan(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<FullScan, String> {
+    let apps = rescan_and_save(&app, &state, true);
+    let skipped = state.skipped_dirs.lock().unwrap().clone();
+    Ok(FullScan { apps, skipped: (!skipped.is_empty()).then_some(skipped) })
+}
+
+/// How old the index is and whether the last refresh could skip re-walking
+/// every scan root.
+#[tauri::command]
+async fn last_scan_info(state: tauri::State<'_, AppState>) -> Result<ScanInfo, String> {
+    let scanned_at = *state.scanned_at.lock().unwrap();
+    Ok(ScanInfo {
+        cache_age_secs: scanned_at.map(|t| now_secs().saturating_sub(t)),
+        from_cache: state.from_cache.load(Ordering::SeqCst),
+    })
+}
+
+/// Leave roots on removable and network drives out of scans (they can stall).
+#[tauri::command]
+async fn set_skip_slow_drives(app: AppHandle, state: tauri::State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    update_config(&app, "skip_slow_drives", |c| c.skip_slow_drives = enabled)?;
+    state.index.lock().unwrap().clear();
+    Ok(())
+}
+
+/// Scan one folder for a transient "This folder" section.
+/// Uses the normal scan rules but leaves the index, caches and config untouched.
+#[tauri::command]
+async fn scan_folder_once(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<AppEntry>, String> {
+    let dir = PathBuf::from(&path);
+    if !dir.is_dir() { return Err(format!("not a directory: {path}")); }
+    let cfg = state.config.lock().unwrap().clone();
+    Ok(finish_index(scan_dir(&dir, &ScanRules::from_config(&cfg), false, &|_| {})).into_iter()
+        .filter(|a| cfg.allows(&a.path))
+        .collect())
+}
+
+/// Distinct categories in the current index with entry counts, flagging the
+/// ones hidden from the default view.
+#[tauri::command]
+async fn get_categories(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<Vec<CategoryInfo>, String> {
+    let apps = indexed_apps(&app, &state);
+    let hidden = state.config.lock().unwrap().hidden_categories.clone();
+    let mut counts: std::collections::BTreeMap<String, usize> = Default::default();
+    for a in &apps {
+        *counts.entry(a.category.clone()).or_default() += 1;
+    }
+    Ok(counts.into_iter()
+        .map(|(name, count)| CategoryInfo { hidden: hidden.contains(&name), name, count })
+        .collect())
+}
+
+/// Hide a category from the default (empty-query) browse list. Hidden
+/// categories stay indexed and searchable.
+#[tauri::command]
+async fn set_category_hidden(app: AppHandle, category: String, hidden: bool) -> Result<(), String> {
+    update_config(&app, "hidden_categories", |c| {
+        c.hidden_categories.retain(|h| *h != category);
+        if hidden { c.hidden_categories.push(category); }
+    })?;
+    Ok(())
+}
+
+/// Virtual "Recently Installed" category: apps first indexed within the last
+/// `days` days (default 14), newest first. Entries age out on their own.
+#[tauri::command]
+async fn get_recently_installed(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    days: Option<u64>,
+) -> Result<Vec<AppEntry>, String> {
+    let apps = indexed_apps(&app, &state);
+    let seen = record_first_seen(&app, &apps);
+    let cutoff = now_secs().saturating_sub(days.unwrap_or(14) * 86_400);
+
+    let mut recent: Vec<(u64, AppEntry)> = apps.into_iter()
+        .filter_map(|a| {
+            let ts = *seen.get(&a.path)?;
+            (ts > 0 && ts >= cutoff).then_some((ts, a))
+        })
+        .collect();
+    recent.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    Ok(recent.into_iter()
+        .map(|(_, a)| AppEntry { category: RECENTLY_INSTALLED.to_string(), ..a })
+        .collect())
+}
+
+/// Resolve many shortcuts to their targets in one call (None = unresolvable).
+/// Served from the resolved-target cache; misses are resolved in bounded batches.
+#[tauri::command]
+async fn resolve_targets(
+    paths: Vec<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Option<String>>, String> {
+    Ok(resolve_targets_cached(&state, &paths))
+}
+
+const UNKNOWN_DRIVE: &str = "Unknown";
+
+/// Drive letter or UNC share a target lives on.
+fn drive_of(target: &str) -> Option<String> {
+    match Path::new(target).components().next()? {
+        std::path::Component::Prefix(p) => Some(p.as_os_str().to_string_lossy().to_uppercase()),
+        _ => None,
+    }
+}
+
+/// Browse apps by where they're installed. Shortcuts are grouped by their
+/// resolved target; anything unresolvable lands in "Unknown" (listed last).
+#[tauri::command]
+async fn get_apps_by_drive(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<Vec<DriveGroup>, String> {
+    let apps = indexed_apps(&app, &state);
+    let paths: Vec<String> = apps.iter().map(|a| a.path.clone()).collect();
+    let targets = resolve_targets_cached(&state, &paths);
+
+    let mut groups: std::collections::BTreeMap<String, Vec<AppEntry>> = Default::default();
+    for (app, target) in apps.into_iter().zip(targets) {
+        let drive = target.as_deref().and_then(drive_of).unwrap_or_else(|| UNKNOWN_DRIVE.to_string());
+        groups.entry(drive).or_default().push(app);
+    }
+    let unknown = groups.remove(UNKNOWN_DRIVE);
+    Ok(groups.into_iter()
+        .chain(unknown.map(|apps| (UNKNOWN_DRIVE.to_string(), apps)))
+        .map(|(drive, apps)| DriveGroup { drive, count: apps.len(), apps })
+        .collect())
+}
+
+// Computed install-folder sizes are reused for this long
+const SIZE_TTL_SECS: u64 = 3600;
+
+/// Folder an app is installed in, judged from its target exe (`...\bin\x.exe`
+/// counts its parent). None for Windows binaries and top-level folders like
+/// `C:\Program Files`, whose size says nothing about one app.
+fn install_dir(target: &str) -> Option<PathBuf> {
+    let mut dir = Path::new(target).parent()?.to_path_buf();
+    if dir.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.eq_ignore_ascii_case("bin")) {
+        dir = dir.parent()?.to_path_buf();
+    }
+    if let Ok(win) = std::env::var("WINDIR") {
+        if dir.to_string_lossy().to_lowercase().starts_with(&win.to_lowercase()) { return None; }
+    }
+    if dir.ancestors().count() < 3 { return None; }
+    Some(dir)
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir).into_iter().filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Largest apps by install-folder size. Sizes are computed in the background
+/// and cached for an hour; apps whose size isn't known yet are left out and an
+/// `app-size-updated` event fires as each folder is measured. Apps sharing a
+/// folder split its size evenly.
+#[tauri::command]
+async fn get_apps_by_size(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<AppSize>, String> {
+    let apps = indexed_apps(&app, &state);
+    let paths: Vec<String> = apps.iter().map(|a| a.path.clone()).collect();
+    let targets = resolve_targets_cached(&state, &paths);
+
+    let mut by_dir: HashMap<PathBuf, Vec<AppEntry>> = HashMap::new();
+    for (entry, target) in apps.into_iter().zip(targets) {
+        if let Some(dir) = target.as_deref().and_then(install_dir) {
+            by_dir.entry(dir).or_default().push(entry);
+        }
+    }
+
+    let now = now_secs();
+    let mut out = Vec::new();
+    let mut stale = Vec::new();
+    {
+        let sizes = state.sizes.lock().unwrap();
+        for (dir, members) in &by_dir {
+            match sizes.get(dir) {
+                Some(&(bytes, at)) if now.saturating_sub(at) < SIZE_TTL_SECS => {
+                    let n = members.len() as u64;
+                    out.extend(members.iter().map(|m| AppSize {
+                        name: m.name.clone(),
+                        path: m.path.clone(),
+                        install_dir: dir.to_string_lossy().to_string(),
+                        bytes: bytes / n,
+                        shared: n > 1,
+                    }));
+                }
+                _ => stale.push(dir.clone()),
+            }
+        }
+    }
+
+    if !stale.is_empty() && !state.sizing.swap(true, Ordering::SeqCst) {
+        std::thread::spawn(move || {
+            let state = app.state::<AppState>();
+            for dir in stale {
+                let bytes = dir_size(&dir);
+                state.sizes.lock().unwrap().insert(dir.clone(), (bytes, now_secs()));
+                let _ = app.emit("app-size-updated", serde_json::json!({
+                    "install_dir": dir.to_string_lossy(),
+                    "bytes": bytes,
+                }));
+            }
+            state.sizing.store(false, Ordering::SeqCst);
+        });
+    }
+
+    out.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    out.truncate(limit.unwrap_or(20));
+    Ok(out)
+}
+
+/// Lowercase alphanumerics only, so "Visual Studio Code" ~ "visualstudiocode".
+fn squash(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+/// %APPDATA% / %LOCALAPPDATA% subfolders that look like they belong to an app
+/// with one of `names`, best first (0 = exact, 1 = one contains the other).
+fn app_data_candidates(names: &[String]) -> Vec<(u8, PathBuf)> {
+    let keys: Vec<String> = names.iter().map(|n| squash(n)).filter(|k| k.len() >= 3).collect();
+    let mut found = Vec::new();
+    for var in ["APPDATA", "LOCALAPPDATA"] {
+        let Ok(root) = std::env::var(var) else { continue };
+        let Ok(entries) = std::fs::read_dir(root) else { continue };
+        for dir in entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()) {
+            let folder = squash(&dir.file_name().unwrap_or_default().to_string_lossy());
+            if folder.len() < 3 { continue; }
+            let score = keys.iter().filter_map(|k| {
+                if *k == folder { Some(0) }
+                else if k.contains(&folder) || folder.contains(k.as_str()) { Some(1) }
+                else { None }
+            }).min();
+            if let Some(score) = score { found.push((score, dir)); }
+        }
+    }
+    found.sort();
+    found
+}
+
+fn open_in_explorer(dir: &Path) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    std::process::Command::new("explorer")
+        .arg(dir)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    #[cfg(not(target_os = "windows"))]
+    let _ = dir;
+    Ok(())
+}
+
+/// Show where an app lives: Explorer opens the shortcut target's folder with
+/// the file selected (folders are simply opened).
+#[tauri::command]
+async fn reveal_in_explorer(path: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    if is_virtual_entry(&path) {
+        return Err(format!("{path} is not a file on disk"));
+    }
+    let target = resolve_targets_cached(&state, std::slice::from_ref(&path)).pop().flatten()
+        .ok_or_else(|| format!("could not resolve shortcut target: {path}"))?;
+    let target = Path::new(&target);
+    if !target.exists() {
+        return Err(format!("target no longer exists: {}", target.display()));
+    }
+    if target.is_dir() {
+        return open_in_explorer(target);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // raw_arg: explorer wants /select,"path" — not the whole switch quoted
+        std::process::Command::new("explorer")
+            .raw_arg(format!("/select,\"{}\"", target.display()))
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Open an app's per-user data folder (e.g. %APPDATA%\Code), guessed from the
+/// shortcut name and target exe. Ambiguous matches are returned unopened so
+/// the UI can offer a choice; with no match, %APPDATA% itself is opened.
+#[tauri::command]
+async fn open_app_data_dir(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<DataDirMatch, String> {
+    let stem = |p: &str| Path::new(p).file_stem().and_then(|s| s.to_str()).map(str::to_string);
+    let target = resolve_targets_cached(&state, std::slice::from_ref(&path)).pop().flatten();
+    let names: Vec<String> = [stem(&path), target.as_deref().and_then(stem)].into_iter().flatten().collect();
+
+    let candidates = app_data_candidates(&names);
+    if let Some((best, _)) = candidates.first() {
+        let top: Vec<&PathBuf> = candidates.iter().filter(|(s, _)| s == best).map(|(_, d)| d).collect();
+        if top.len() == 1 {
+            open_in_explorer(top[0])?;
+            return Ok(DataDirMatch::Opened { path: top[0].to_string_lossy().to_string() });
+        }
+        return Ok(DataDirMatch::Ambiguous {
+            choices: top.iter().map(|d| d.to_string_lossy().to_string()).collect(),
+        });
+    }
+
+    let root = std::env::var("APPDATA").map_err(|e| e.to_string())?;
+    open_in_explorer(Path::new(&root))?;
+    Ok(DataDirMatch::Fallback { path: root })
+}
+
+/// Open an app's homepage / support / update link from its uninstall entry
+/// (URLInfoAbout, HelpLink, URLUpdateInfo) in the default browser.
+#[tauri::command]
+async fn open_app_url(
+    path: String,
+    kind: UrlKind,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let name = Path::new(&path).file_stem().and_then(|s| s.to_str()).unwrap_or(&path).to_string();
+    let target = resolve_targets_cached(&state, std::slice::from_ref(&path)).pop().flatten();
+    let entry = find_uninstall_entry(&name, target.as_deref())
+        .ok_or_else(|| format!("no installed-program record found for {name}"))?;
+
+    let (url, label) = match kind {
+        UrlKind::Homepage => (entry.url_info_about, "homepage"),
+        UrlKind::Support  => (entry.help_link, "support"),
+        UrlKind::Update   => (entry.url_update_info, "update"),
+    };
+    let url = url
+        .filter(|u| u.starts_with("http://") || u.starts_with("https://"))
+        .ok_or_else(|| format!("{} has no {label} link recorded", entry.display_name))?;
+
+    #[cfg(target_os = "windows")]
+    std::process::Command::new("explorer")
+        .arg(&url)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    Ok(url)
+}
+
+/// Fuzzy search over app names (and pinyin for Chinese names), best score
+/// first, with matched char indices for highlighting. An empty query returns
+/// every app in frecency order. `limit` caps the result count (default: all);
+/// `category` (a `get_categories` name) restricts the search to that category.
+#[tauri::command]
+async fn search_apps(
+    app: AppHandle,
+    query: String,
+    limit: Option<usize>,
+    category: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SearchHit>, String> {
+    let mut apps = indexed_apps(&app, &state);
+    if let Some(category) = &category {
+        apps.retain(|a| a.category.eq_ignore_ascii_case(category));
+    }
+    let limit = limit.unwrap_or(usize::MAX);
+    let query = query.trim();
+    if query.is_empty() {
+        // Browsing everything (no category picked) leaves hidden categories out
+        if category.is_none() { apps = without_hidden_categories(apps, &state); }
+        return Ok(frecency_order(apps, &state).into_iter()
+            .take(limit)
+            .map(|r| SearchHit { app: r.app, score: 0, indices: Vec::new() })
+            .collect());
+    }
+
+    mark_pinned(&mut apps, &state.config.lock().unwrap().pins);
+    let aliases = state.aliases.lock().unwrap().clone();
+    let mut hits = search_hits(apps, query, &aliases);
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+/// Give an app a short alias (`vs` → Visual Studio) that search_apps always
+/// ranks first. Aliases are case-insensitive and unique across apps.
+#[tauri::command]
+async fn set_alias(
+    app: AppHandle,
+    path: String,
+    alias: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let alias = alias.trim().to_lowercase();
+    if alias.is_empty() {
+        return Err("alias cannot be empty".into());
+    }
+    let mut aliases = state.aliases.lock().unwrap();
+    if let Some((other, _)) = aliases.iter().find(|(p, a)| **a == alias && **p != path) {
+        let name = state.index.lock().unwrap().iter()
+            .find(|a| &a.path == other)
+            .map_or_else(|| other.clone(), |a| a.name.clone());
+        return Err(format!("alias \"{alias}\" is already used by {name}"));
+    }
+    aliases.insert(path, alias);
+    save_aliases(&app, &aliases)
+}
+
+/// Drop an app's alias; no-op if it has none.
+#[tauri::command]
+async fn remove_alias(app: AppHandle, path: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let mut aliases = state.aliases.lock().unwrap();
+    if aliases.remove(&path).is_some() {
+        save_aliases(&app, &aliases)?;
+    }
+    Ok(())
+}
+
+/// Resolve a query to one app for scripting / launch-by-name: the top
+/// `search_apps` hit. Returns None unless that hit is an exact alias or its
+/// name matches at least as well as `max_rank` (0 exact, 1 prefix,
+/// 2 substring, 3 initials; default 1).
+#[tauri::command]
+async fn resolve_best(
+    app: AppHandle,
+    query: String,
+    max_rank: Option<u8>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<AppEntry>, String> {
+    let query = query.trim();
+    if query.is_empty() { return Ok(None); }
+    let apps = indexed_apps(&app, &state);
+    let aliases = state.aliases.lock().unwrap().clone();
+    let limit = max_rank.unwrap_or(DEFAULT_RESOLVE_MAX_RANK);
+    Ok(search_hits(apps, query, &aliases).into_iter()
+        .next()
+        .filter(|hit| hit.score == ALIAS_SCORE || match_rank(&hit.app.name, query).is_some_and(|r| r <= limit))
+        .map(|hit| hit.app))
+}
+
+/// One indexed entry with `version` / `publisher` filled in from its target
+/// exe's version resource (left None when there isn't one). Read on demand
+/// for the selected item so bulk scans stay fast.
+#[tauri::command]
+async fn get_app_details(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<AppEntry, String> {
+    let mut entry = indexed_apps(&app, &state).into_iter()
+        .find(|a| a.path == path)
+        .ok_or_else(|| format!("not indexed: {path}"))?;
+    if !is_virtual_entry(&path) {
+        let target = resolve_targets_cached(&state, std::slice::from_ref(&path)).pop().flatten()
+            .unwrap_or_else(|| path.clone());
+        if let Some((version, publisher)) = file_version_info(&target) {
+            entry.version = version;
+            entry.publisher = publisher;
+        }
+    }
+    Ok(entry)
+}
+
+/// Put an app's full path on the clipboard (for scripting) instead of launching it.
+#[tauri::command]
+async fn copy_path(app: AppHandle, path: String) -> Result<(), String> {
+    app.clipboard().write_text(path).map_err(|e| format!("clipboard unavailable: {e}"))
+}
+
+/// Inline calculator: `12*(3+4)` → "84". None when the query isn't arithmetic,
+/// so the frontend only shows a result row for real expressions.
+#[tauri::command]
+async fn evaluate_expression(query: String) -> Result<Option<String>, String> {
+    Ok(evaluate(&query)?.map(format_number))
+}
+
+/// PNG icon for `path`: the user-set icon, else the on-disk cache, else a
+/// fresh extraction (written to the cache).
+fn lookup_icon(app: &AppHandle, state: &AppState, path: &str, size: u32) -> Option<String> {
+    if let Some(custom) = read_custom_icon(app, path, size) { return Some(custom); }
+    let target = resolve_targets_cached(state, &[path.to_string()]).pop().flatten();
+    let file = icon_cache_dir(app).and_then(|d| icon_cache_file(&d, path, target, size));
+    if let Some(hit) = file.as_deref().and_then(|f| read_cached_icon(&state.icon_counts, f)) {
+        return Some(hit);
+    }
+    let png = extract_icon_counted(&state.icon_counts, path, size);
+    if let (Some(f), Some(p)) = (&file, &png) { write_cached_icon(f, p); }
+    png
+}
+
+/// Extract icon for a single app on demand (called per-item by frontend).
+/// A user-set icon wins; otherwise served from the on-disk icon cache when the app hasn't changed.
+/// `size` is the edge length in px (default 48, max 256).
+/// `format: "webp"` returns a smaller WebP payload, falling back to PNG.
+#[tauri::command]
+async fn get_icon(
+    app: AppHandle,
+    path: String,
+    format: Option<IconFormat>,
+    size: Option<u32>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let size = size.unwrap_or(DEFAULT_ICON_SIZE).clamp(16, MAX_ICON_SIZE);
+    let png = lookup_icon(&app, &state, &path, size);
+    Ok(match format.unwrap_or_default() {
+        IconFormat::Png  => png,
+        IconFormat::Webp => png.map(|p| png_to_webp(&p).unwrap_or(p)),
+    })
+}
+
+/// Use a user-supplied PNG (base64, optionally as a data: URL) as the icon for
+/// `path`. Anything that doesn't decode as a PNG image is rejected.
+#[tauri::command]
+async fn set_custom_icon(app: AppHandle, path: String, icon_base64: String) -> Result<(), String> {
+    use base64::Engine;
+    let raw = icon_base64.trim();
+    let raw = raw.strip_prefix("data:image/png;base64,").unwrap_or(raw);
+    let bytes = base64::engine::general_purpose::STANDARD.decode(raw)
+        .map_err(|e| format!("icon is not valid base64: {e}"))?;
+    image::load_from_memory_with_format(&bytes, image::ImageFormat::Png)
+        .map_err(|e| format!("icon is not a valid PNG: {e}"))?;
+    let file = custom_icon_file(&app, &path).ok_or("config directory unavailable")?;
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&file, bytes).map_err(|e| e.to_string())?;
+    app.state::<AppState>().accents.lock().unwrap().remove(&path);
+    Ok(())
+}
+
+/// Go back to the extracted icon for `path`. No-op if none was set.
+#[tauri::command]
+async fn clear_custom_icon(app: AppHandle, path: String) -> Result<(), String> {
+    let file = custom_icon_file(&app, &path).ok_or("config directory unavailable")?;
+    match std::fs::remove_file(&file) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+        _ => {}
+    }
+    app.state::<AppState>().accents.lock().unwrap().remove(&path);
+    Ok(())
+}
+
+/// Prime icons for a whole list at once. User-set icons and disk-cache hits are served directly,
+/// the rest are extracted natively, and any left over go through one
+/// PowerShell run per 64 paths instead of one per app. Emits `icon-extraction-summary` with the updated stats when done.
+#[tauri::command]
+async fn get_icons(
+    app: AppHandle,
+    paths: Vec<String>,
+    size: Option<u32>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<(String, Option<String>)>, String> {
+    let size = size.unwrap_or(DEFAULT_ICON_SIZE).clamp(16, MAX_ICON_SIZE);
+    let dir = icon_cache_dir(&app);
+    let files: Vec<Option<PathBuf>> = paths.iter().zip(resolve_targets_cached(&state, &paths))
+        .map(|(p, t)| dir.as_deref().and_then(|d| icon_cache_file(d, p, t, size)))
+        .collect();
+    let mut icons: Vec<Option<String>> = paths.iter().zip(&files)
+        .map(|(p, f)| read_custom_icon(&app, p, size)
+            .or_else(|| f.as_deref().and_then(|f| read_cached_icon(&state.icon_counts, f))))
+        .collect();
+
+    // Native extraction first; only what it can't read goes to PowerShell
+    let mut extracted: Vec<(usize, Option<String>)> = Vec::new();
+    let mut fallback = Vec::new();
+    for i in (0..paths.len()).filter(|&i| icons[i].is_none()) {
+        match extract_icon_native(&paths[i], size) {
+            Some(icon) => extracted.push((i, Some(icon))),
+            None if is_url_entry(&paths[i]) => extracted.push((i, None)),
+            None => fallback.push(i),
+        }
+    }
+    // Batches run side by side, as many at once as PowerShell job slots allow
+    std::thread::scope(|scope| {
+        let running: Vec<_> = fallback.chunks(ICON_CHUNK)
+            .map(|chunk| {
+                let batch: Vec<String> = chunk.iter().map(|&i| paths[i].clone()).collect();
+                (chunk, scope.spawn(move || extract_icons_powershell(&batch)))
+            })
+            .collect();
+        for (chunk, job) in running {
+            let icons = job.join().unwrap_or_else(|_| vec![None; chunk.len()]);
+            let scaled = icons.into_iter().map(|icon| icon.map(|p| scale_png(p, size)));
+            extracted.extend(chunk.iter().copied().zip(scaled));
+        }
+    });
+    for (i, icon) in extracted {
+        count_icon(&state.icon_counts, &icon);
+        if let (Some(f), Some(p)) = (&files[i], &icon) { write_cached_icon(f, p); }
+        icons[i] = icon;
+    }
+    let _ = app.emit("icon-extraction-summary", icon_stats(&state.icon_counts));
+    Ok(paths.into_iter().zip(icons).collect())
+}
+
+/// Aggregate icon outcomes so a broken backend (e.g. PowerShell blocked by
+/// policy) shows up as a high failure rate instead of silent avatars.
+#[tauri::command]
+async fn get_icon_stats(state: tauri::State<'_, AppState>) -> Result<IconStats, String> {
+    Ok(icon_stats(&state.icon_counts))
+}
+
+/// Dominant color of an app's icon for the result glow / avatar tint.
+/// Taken from the same icon `get_icon` serves (user-set, cached or extracted);
+/// cached per path until the custom icon changes. None when there's no icon.
+#[tauri::command]
+async fn get_icon_accent(
+    app: AppHandle,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    if let Some(hit) = state.accents.lock().unwrap().get(&path) {
+        return Ok(hit.clone());
+    }
+    let accent = lookup_icon(&app, &state, &path, DEFAULT_ICON_SIZE).and_then(|b64| accent_color(&b64));
+    state.accents.lock().unwrap().insert(path, accent.clone());
+    Ok(accent)
+}
+
+/// Open `file` the way Explorer would (ShellExecuteW with `verb`, usually "open";
+/// "runas" elevates). No console, and no cmd.exe parsing, so `&`, `^` and `%`
+/// in paths are passed through as-is. A declined UAC prompt is an error
+/// starting with "Cancelled:".
+#[cfg(target_os = "windows")]
+fn shell_execute(verb: &str, file: &str, args: Option<&str>, dir: Option<&str>) -> Result<(), String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::{GetLastError, ERROR_CANCELLED, HWND};
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+    let (args, dir) = (args.map(HSTRING::from), dir.map(HSTRING::from));
+    let opt = |h: &Option<HSTRING>| h.as_ref().map_or(PCWSTR::null(), |h| PCWSTR(h.as_ptr()));
+    let code = unsafe {
+        ShellExecuteW(HWND::default(), &HSTRING::from(verb), &HSTRING::from(file),
+            opt(&args), opt(&dir), SW_SHOWNORMAL)
+    };
+    // Values <= 32 are error codes (SE_ERR_*)
+    let code = code.0 as isize;
+    if code <= 32 {
+        if unsafe { GetLastError() } == ERROR_CANCELLED {
+            return Err(format!("Cancelled: {file} was not started"));
+        }
+        return Err(format!("failed to launch {file} (ShellExecute error {code})"));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_execute(_: &str, _: &str, _: Option<&str>, _: Option<&str>) -> Result<(), String> { Ok(()) }
+
+/// What to run for `path` as (file, args, working dir): a shortcut's target
+/// with its stored arguments and working directory, or `path` itself if it
+/// isn't a shortcut or its target is gone. `args` / `dir` override the stored
+/// ones; a bare .exe with no working directory runs from its own folder.
+fn launch_target(state: &AppState, path: &str, args: Option<&str>, dir: Option<&str>) -> (String, Option<String>, Option<String>) {
+    let link = match state.index.lock().unwrap().iter().find(|a| a.path == path) {
+        Some(a) => ShortcutInfo { target: a.target.clone(), args: a.args.clone(), working_dir: a.working_dir.clone() },
+        None if is_lnk(path) => read_shortcut(path).unwrap_or_default(),
+        None => ShortcutInfo::default(),
+    };
+    resolve_launch(link, path, args, dir)
+}
+
+/// `launch_target` once the shortcut (if any) behind `path` has been read.
+fn resolve_launch(link: ShortcutInfo, path: &str, args: Option<&str>, dir: Option<&str>) -> (String, Option<String>, Option<String>) {
+    let (file, stored_args, stored_dir) = match link {
+        ShortcutInfo { target: Some(target), args, working_dir } if Path::new(&target).exists() => {
+            (target, args, working_dir)
+        }
+        _ => (path.to_string(), None, None),
+    };
+    let args = args.map(str::to_string).or(stored_args);
+    let dir = dir.map(str::to_string).or(stored_dir).or_else(|| {
+        file.to_lowercase().ends_with(".exe")
+            .then(|| Path::new(&file).parent().map(|p| p.to_string_lossy().to_string()))
+            .flatten()
+    });
+    (file, args, dir)
+}
+
+/// Open `path` (see `launch_target`) with ShellExecuteW's `verb`.
+fn launch_entry(state: &AppState, path: &str, verb: &str, args: Option<&str>, dir: Option<&str>) -> Result<(), String> {
+    let (file, args, dir) = launch_target(state, path, args, dir);
+    shell_execute(verb, &file, args.as_deref(), dir.as_deref())
+}
+
+/// Open `path` by kind: system action, saved command, shell URI, URL or file.
+fn open_entry(state: &AppState, path: &str, confirm: bool, args: Option<&str>, dir: Option<&str>) -> Result<(), String> {
+    if let Some(action) = path.strip_prefix(SYSTEM_SCHEME) {
+        run_system_action(action, confirm)
+    } else if let Some(name) = path.strip_prefix(COMMAND_SCHEME) {
+        let entry = state.config.lock().unwrap().commands.iter().find(|c| c.name == name).cloned()
+            .ok_or_else(|| format!("no saved command named {name}"))?;
+        run_command_entry(&entry)
+    } else if is_shell_uri(path) || has_scheme(path, "ms-settings:") {
+        // Store apps (shell:AppsFolder\...) and Settings pages are opened through Explorer
+        open_in_explorer(Path::new(path))
+    } else if is_url_entry(path) {
+        // Bookmarks open in the default browser, steam:// in the Steam client
+        shell_execute("open", path, None, None)
+    } else {
+        launch_entry(state, path, "open", args, dir)
+    }
+}
+
+/// Pass `result` through; on failure emit `launch-error` and, with
+/// `launch_error_toast` on, show a toast too, since the launcher has usually
+/// hidden by the time a launch fails.
+fn report_launch_error<T>(app: &AppHandle, path: &str, result: Result<T, String>) -> Result<T, String> {
+    let Err(message) = &result else { return result };
+    // A pending confirmation is a question for the UI, not a failure
+    if message.starts_with("Confirm:") { return result; }
+    let _ = app.emit("launch-error", LaunchError { path: path.to_string(), message: message.clone() });
+
+    let state = app.state::<AppState>();
+    if state.config.lock().unwrap().launch_error_toast {
+        let name = state.index.lock().unwrap().iter().find(|a| a.path == path).map(|a| a.name.clone())
+            .unwrap_or_else(|| Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or(path).to_string());
+        let title = current_strings(&state).launch_failed.replace("{name}", &name);
+        let _ = app.notification().builder().title(title).body(message).show();
+    }
+    result
+}
+
+/// Run a built-in `system:` action. Sign out / shut down / restart refuse to
+/// run without `confirm` so a stray Enter can't end the session.
+fn run_system_action(action: &str, confirm: bool) -> Result<(), String> {
+    if DESTRUCTIVE_ACTIONS.contains(&action) && !confirm {
+        return Err(format!("Confirm: {action} needs confirmation"));
+    }
+    let (program, args): (&str, &[&str]) = match action {
+        "lock"     => ("rundll32.exe", &["user32.dll,LockWorkStation"]),
+        "sleep"    => ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"]),
+        "signout"  => ("shutdown.exe", &["/l"]),
+        "shutdown" => ("shutdown.exe", &["/s", "/t", "0"]),
+        "restart"  => ("shutdown.exe", &["/r", "/t", "0"]),
+        _ => return Err(format!("unknown system action: {action}")),
+    };
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        std::process::Command::new(program)
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = (program, args);
+    Ok(())
+}
+
+/// Run a saved command through cmd.exe: silently, or in a console that stays
+/// open (`cmd /K`) when `run_in_terminal` is set.
+fn run_command_entry(entry: &CommandEntry) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+        let (switch, flags) = if entry.run_in_terminal { ("/K", CREATE_NEW_CONSOLE) } else { ("/C", CREATE_NO_WINDOW) };
+        // raw_arg: cmd parses the rest of the line itself; don't re-quote it
+        std::process::Command::new("cmd.exe")
+            .arg(switch)
+            .raw_arg(&entry.command)
+            .creation_flags(flags)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = entry;
+    Ok(())
+}
+
+/// Launch an indexed entry. `confirm` must be true for destructive System
+/// entries (sign out, shut down, restart); otherwise the error starts with "Confirm:".
+/// `args` and `working_dir` override a shortcut's own when launching a file.
+#[tauri::command]
+async fn launch_app(
+    app: AppHandle,
+    path: String,
+    confirm: Option<bool>,
+    args: Option<String>,
+    working_dir: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let result = ensure_allowed(&state, &path).and_then(|()| {
+        open_entry(&state, &path, confirm.unwrap_or(false), args.as_deref(), working_dir.as_deref())
+    });
+    if result.is_ok() { record_launch(&app, &path); }
+    report_launch_error(&app, &path, result)
+}
+
+/// Err unless `path` is something the "runas" verb can launch: URLs, shell,
+/// system and command entries can't be elevated, and ClickOnce apps always
+/// run as the current user.
+fn ensure_elevatable(path: &str) -> Result<(), String> {
+    if is_virtual_entry(path) || path.to_lowercase().ends_with(".appref-ms") {
+        return Err(format!("{path} can't be run as administrator"));
+    }
+    Ok(())
+}
+
+/// Run an app as administrator (UAC prompt). If the user declines, the error
+/// starts with "Cancelled:" so the UI can treat it as a non-event.
+#[tauri::command]
+async fn launch_app_elevated(
+    app: AppHandle,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let result = ensure_allowed(&state, &path)
+        .and_then(|()| ensure_elevatable(&path))
+        .and_then(|()| launch_entry(&state, &path, "runas", None, None));
+    if result.is_ok() { record_launch(&app, &path); }
+    report_launch_error(&app, &path, result)
+}
+
+// Upper bound on launch_and_notify tasks being waited on at once
+const MAX_NOTIFY_WAITERS: usize = 4;
+
+/// Launch a task (script, installer, build) and show a native notification
+/// with its exit code once it finishes. The wait runs in the background.
+/// Shortcuts run their target with the stored arguments (then `args`) and
+/// working directory; URLs, shell/system/command entries and other files that
+/// aren't programs are rejected, as there's no process to wait on.
+#[tauri::command]
+async fn launch_and_notify(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    args: Option<Vec<String>>,
+) -> Result<(), String> {
+    report_launch_error(&app, &path, ensure_allowed(&state, &path))?;
+    if is_virtual_entry(&path) {
+        return report_launch_error(&app, &path, Err(format!("{path} isn't a program, so it can't be waited on")));
+    }
+    let (file, link_args, dir) = launch_target(&state, &path, None, None);
+    let ext = Path::new(&file).extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    if !matches!(ext.as_str(), "exe" | "bat" | "cmd" | "com") {
+        return report_launch_error(&app, &path,
+            Err(format!("{path} doesn't resolve to a program (.exe, .bat, .cmd), so it can't be waited on")));
+    }
+    let mut cmd = tokio::process::Command::new(&file);
+    // Shortcut arguments are one pre-quoted string; pass them through untouched
+    #[cfg(target_os = "windows")]
+    if let Some(a) = &link_args { cmd.raw_arg(a); }
+    #[cfg(not(target_os = "windows"))]
+    let _ = link_args;
+    cmd.args(args.unwrap_or_default());
+    if let Some(d) = &dir { cmd.current_dir(d); }
+
+    if state.waiters.fetch_add(1, Ordering::SeqCst) >= MAX_NOTIFY_WAITERS {
+        state.waiters.fetch_sub(1, Ordering::SeqCst);
+        return Err(format!("too many tasks already running (max {MAX_NOTIFY_WAITERS})"));
+    }
+    let spawned = cmd.spawn();
+    let mut child = match report_launch_error(&app, &path, spawned.map_err(|e| e.to_string())) {
+        Ok(c) => c,
+        Err(e) => {
+            state.waiters.fetch_sub(1, Ordering::SeqCst);
+            return Err(e);
+        }
+    };
+
+    record_launch(&app, &path);
+    let name = Path::new(&path).file_stem().and_then(|s| s.to_str()).unwrap_or(&path).to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let t = current_strings(&state);
+        let body = match child.wait().await {
+            Ok(status) => match status.code() {
+                Some(code) => t.task_exited.replace("{name}", &name).replace("{code}", &code.to_string()),
+                None       => t.task_finished.replace("{name}", &name),
+            },
+            Err(e) => format!("{name}: {e}"),
+        };
+        state.waiters.fetch_sub(1, Ordering::SeqCst);
+        let _ = app.notification().builder().title("QuickLaunch").body(body).show();
+    });
+    Ok(())
+}
+
+/// Hide after the fade-out delay (see `window-will-hide`).
+#[tauri::command]
+async fn hide_window(window: WebviewWindow) -> Result<(), String> {
+    hide_with_fade(&window);
+    Ok(())
+}
+
+#[tauri::command]
+async fn show_window(window: WebviewWindow) -> Result<(), String> {
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())?;
+    mark_shown(&window);
+    Ok(())
+}
+
+/// Show the launcher with one app highlighted (not launched), for guided
+/// "here's the app" flows. `id_or_path` matches an entry path or exact name.
+/// Emits `select-app` with the entry, or `select-app-missing` if it isn't indexed.
+#[tauri::command]
+async fn show_with_selection(
+    window: WebviewWindow,
+    state: tauri::State<'_, AppState>,
+    id_or_path: String,
+) -> Result<bool, String> {
+    let found = indexed_apps(window.app_handle(), &state).into_iter()
+        .find(|a| a.path.eq_ignore_ascii_case(&id_or_path) || a.name.eq_ignore_ascii_case(&id_or_path));
+
+    center_window_on_screen(&window);
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())?;
+    mark_shown(&window);
+    let _ = window.emit("reset-search", ());
+    match &found {
+        Some(app) => { let _ = window.emit("select-app", app); }
+        None      => { let _ = window.emit("select-app-missing", &id_or_path); }
+    }
+    Ok(found.is_some())
+}
+
+/// Save the current window position to shared state and to disk.
+#[tauri::command]
+async fn save_window_pos(
+    app: AppHandle,
+    window: WebviewWindow,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let pos = window.outer_position().map_err(|e| e.to_string())?;
+    let on = window.available_monitors().unwrap_or_default().into_iter()
+        .find(|m| monitor_contains(m, pos.x, pos.y));
+    let pos = match on.as_ref().and_then(|m| Some((m.name()?.clone(), m.position()))) {
+        Some((name, origin)) => WindowPos { x: pos.x - origin.x, y: pos.y - origin.y, monitor: Some(name) },
+        None => WindowPos { x: pos.x, y: pos.y, monitor: None },
+    };
+    persist_window_pos(&app, &pos)?;
+    *state.last_pos.lock().unwrap() = Some(pos);
+    Ok(())
+}
+
+/// Restore the last saved position on the monitor it was saved on, kept inside
+/// that monitor's bounds. Centers on the primary monitor if that monitor is
+/// gone, and centers as usual if nothing was saved.
+#[tauri::command]
+async fn restore_window_pos(
+    window: WebviewWindow,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let pos = state.last_pos.lock().unwrap().clone();
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let target = match pos {
+        Some(WindowPos { x, y, monitor: Some(name) }) => {
+            let saved_on = window.available_monitors().unwrap_or_default().into_iter()
+                .find(|m| m.name() == Some(&name));
+            match saved_on {
+                Some(m) => Some(position_within(&m, size, x, y)),
+                None => window.primary_monitor().ok().flatten().map(|m| anchor_position(&m, size)),
+            }
+        }
+        Some(p) if position_on_monitor(&window, None, p.x, p.y) => Some(tauri::PhysicalPosition::new(p.x, p.y)),
+        _ => None,
+    };
+    match target {
+        Some(p) => window.set_position(p).map_err(|e| e.to_string()),
+        None => { center_window_on_screen(&window); Ok(()) }
+    }
+}
+
+/// Let mouse events pass through the window (keyboard-driven overlay mode).
+/// Showing the window from the tray always turns this back off.
+#[tauri::command]
+async fn set_click_through(window: WebviewWindow, enabled: bool) -> Result<(), String> {
+    window.set_ignore_cursor_events(enabled).map_err(|e| e.to_string())
+}
+
+/// QA aid: show the window at its anchor position on each monitor in turn
+/// so placement can be checked by eye. Compiled out of release builds.
+#[cfg(debug_assertions)]
+#[tauri::command]
+async fn cycle_monitors(window: WebviewWindow) -> Result<(), String> {
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    window.show().map_err(|e| e.to_string())?;
+    for m in &monitors {
+        eprintln!("[QuickLaunch] Anchoring on {}", m.name().map(String::as_str).unwrap_or("?"));
+        window.set_position(anchor_position(m, size)).map_err(|e| e.to_string())?;
+        tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    }
+    center_window_on_screen(&window);
+    Ok(())
+}
+
+/// Capture position, size, monitor, visibility, pin and z-order in one snapshot.
+#[tauri::command]
+async fn get_window_state(window: WebviewWindow) -> Result<WindowState, String> {
+    let pos  = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let monitor = window.current_monitor().map_err(|e| e.to_string())?
+        .and_then(|m| m.name().cloned());
+    Ok(WindowState {
+        x: pos.x,
+        y: pos.y,
+        width: size.width,
+        height: size.height,
+        monitor,
+        visible: window.is_visible().map_err(|e| e.to_string())?,
+        always_on_top: window.is_always_on_top().map_err(|e| e.to_string())?,
+        pinned: !window.state::<AppState>().config.lock().unwrap().hide_on_blur,
+    })
+}
+
+/// Re-apply a snapshot from `get_window_state`.
+/// If the saved monitor is gone (or the position is off-screen) the window is re-centered.
+/// The pin is left alone while `hide_on_blur` is locked by machine policy.
+#[tauri::command]
+async fn apply_window_state(window: WebviewWindow, state: WindowState) -> Result<(), String> {
+    window.set_always_on_top(state.always_on_top).map_err(|e| e.to_string())?;
+    let app = window.app_handle();
+    let (hide_on_blur, locked) = {
+        let s = app.state::<AppState>();
+        let hide_on_blur = s.config.lock().unwrap().hide_on_blur;
+        (hide_on_blur, s.locked.lock().unwrap().contains("hide_on_blur"))
+    };
+    if hide_on_blur == state.pinned && !locked {
+        update_config(app, "hide_on_blur", |c| c.hide_on_blur = !state.pinned)?;
+    }
+    window.set_size(tauri::PhysicalSize::new(state.width, state.height))
+        .map_err(|e| e.to_string())?;
+
+    if position_on_monitor(&window, state.monitor.as_deref(), state.x, state.y) {
+        window.set_position(tauri::PhysicalPosition::new(state.x, state.y))
+            .map_err(|e| e.to_string())?;
+    } else {
+        center_window_on_screen(&window);
+    }
+
+    if state.visible {
+        window.show().map_err(|e| e.to_string())?;
+        window.set_focus().map_err(|e| e.to_string())?;
+        mark_shown(&window);
+    } else {
+        window.hide().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_portableapps_root(state: tauri::State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state.config.lock().unwrap().portableapps_root.clone())
+}
+
+/// Set (or clear with None) the PortableApps.com folder to index.
+#[tauri::command]
+async fn set_portableapps_root(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    if let Some(p) = &path {
+        if !Path::new(p).is_dir() { return Err(format!("not a directory: {p}")); }
+    }
+    update_config(&app, "portableapps_root", |c| c.portableapps_root = path)?;
+    app.state::<AppState>().index.lock().unwrap().clear();
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_scan_dirs(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.config.lock().unwrap().scan_dirs.clone())
+}
+
+/// Replace the extra folders to index (e.g. `D:\Tools`). Folders that don't
+/// exist are not saved and are returned so the UI can flag them.
+#[tauri::command]
+async fn set_scan_dirs(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    dirs: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let (valid, rejected): (Vec<String>, Vec<String>) = dirs.into_iter().partition(|d| Path::new(d).is_dir());
+    update_config(&app, "scan_dirs", |c| c.scan_dirs = valid)?;
+    state.index.lock().unwrap().clear();
+    Ok(rejected)
+}
+
+#[tauri::command]
+async fn get_hide_on_blur(state: tauri::State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.config.lock().unwrap().hide_on_blur)
+}
+
+/// Turn hide-on-focus-loss on or off; the focus listener reads it live.
+#[tauri::command]
+async fn set_hide_on_blur(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_config(&app, "hide_on_blur", |c| c.hide_on_blur = enabled)?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_fade_ms(state: tauri::State<'_, AppState>) -> Result<u64, String> {
+    Ok(state.config.lock().unwrap().fade_ms)
+}
+
+/// How long hides wait for the frontend's fade-out (0 = hide at once, max 1000).
+/// Returns the value actually stored.
+#[tauri::command]
+async fn set_fade_ms(app: AppHandle, ms: u64) -> Result<u64, String> {
+    let ms = ms.min(MAX_FADE_MS);
+    update_config(&app, "fade_ms", |c| c.fade_ms = ms)?;
+    Ok(ms)
+}
+
+/// Turn the toast shown alongside `launch-error` on or off.
+#[tauri::command]
+async fn set_launch_error_toast(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_config(&app, "launch_error_toast", |c| c.launch_error_toast = enabled)?;
+    Ok(())
+}
+
+/// Current launcher size in logical pixels.
+#[tauri::command]
+async fn get_window_size(window: WebviewWindow) -> Result<WindowSize, String> {
+    let scale = window.scale_factor().map_err(|e| e.to_string())?;
+    let size = window.inner_size().map_err(|e| e.to_string())?.to_logical::<u32>(scale);
+    Ok(WindowSize { width: size.width, height: size.height })
+}
+
+/// Resize the launcher (logical pixels, clamped to 400×240–2400×1600), save it
+/// for the next start and re-center. Returns the size actually applied.
+#[tauri::command]
+async fn set_window_size(app: AppHandle, window: WebviewWindow, width: u32, height: u32) -> Result<WindowSize, String> {
+    let size = clamp_window_size(WindowSize { width, height });
+    update_config(&app, "window_size", |c| c.window_size = Some(size))?;
+    apply_window_size(&window, size)?;
+    center_window_on_screen(&window);
+    Ok(size)
+}
+
+/// Keep the launcher above other windows, or let it be covered like a normal
+/// window. Either way, summoning it still brings it to the front (it's shown,
+/// then focused).
+#[tauri::command]
+async fn set_always_on_top(app: AppHandle, window: WebviewWindow, enabled: bool) -> Result<(), String> {
+    update_config(&app, "always_on_top", |c| c.always_on_top = enabled)?;
+    window.set_always_on_top(enabled).map_err(|e| e.to_string())
+}
+
+/// Switch the window backdrop ("acrylic", "mica", "tabbed" or "none") and
+/// save it. Returns the kind actually applied, which differs from `kind` when
+/// this Windows version doesn't support it.
+#[tauri::command]
+async fn set_backdrop(app: AppHandle, window: WebviewWindow, kind: String) -> Result<String, String> {
+    let kind = kind.to_lowercase();
+    if backdrop_type(&kind).is_none() {
+        return Err(format!("unknown backdrop: {kind} (expected acrylic, mica, tabbed or none)"));
+    }
+    update_config(&app, "backdrop", |c| c.backdrop = kind.clone())?;
+    Ok(apply_backdrop(&window, &kind).to_string())
+}
+
+/// Save a shell snippet (e.g. `ipconfig /flushdns`) as a launchable entry
+/// under "Commands". Names must be unique.
+#[tauri::command]
+async fn add_command_entry(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    name: String,
+    command: String,
+    run_in_terminal: bool,
+) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || command.trim().is_empty() {
+        return Err("name and command are required".into());
+    }
+    if state.config.lock().unwrap().commands.iter().any(|c| c.name == name) {
+        return Err(format!("a command named {name} already exists"));
+    }
+    update_config(&app, "commands", |c| c.commands.push(CommandEntry { name, command, run_in_terminal }))?;
+    state.index.lock().unwrap().clear();
+    Ok(())
+}
+
+#[tauri::command]
+async fn remove_command_entry(app: AppHandle, state: tauri::State<'_, AppState>, name: String) -> Result<(), String> {
+    update_config(&app, "commands", |c| c.commands.retain(|e| e.name != name))?;
+    state.index.lock().unwrap().clear();
+    Ok(())
+}
+
+/// Pinned app paths in display order.
+#[tauri::command]
+async fn get_pinned(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.config.lock().unwrap().pins.clone())
+}
+
+/// Pin an app to the top of get_apps_ranked; new pins go last. No-op if pinned.
+#[tauri::command]
+async fn pin_app(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let config = update_config(&app, "pins", |c| {
+        if !c.pins.contains(&path) { c.pins.push(path); }
+    })?;
+    Ok(config.pins)
+}
+
+#[tauri::command]
+async fn unpin_app(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let config = update_config(&app, "pins", |c| c.pins.retain(|p| *p != path))?;
+    Ok(config.pins)
+}
+
+/// Reorder pins after a drag-and-drop. Paths that aren't pinned are ignored;
+/// pins missing from `paths` keep their relative order after the listed ones.
+#[tauri::command]
+async fn reorder_pins(app: AppHandle, paths: Vec<String>) -> Result<Vec<String>, String> {
+    let config = update_config(&app, "pins", |c| {
+        let mut ordered: Vec<String> = Vec::new();
+        for p in paths {
+            if c.pins.contains(&p) && !ordered.contains(&p) { ordered.push(p); }
+        }
+        let rest: Vec<String> = c.pins.iter().filter(|p| !ordered.contains(p)).cloned().collect();
+        ordered.extend(rest);
+        c.pins = ordered;
+    })?;
+    Ok(config.pins)
+}
+
+#[tauri::command]
+async fn get_junk_filter(state: tauri::State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.config.lock().unwrap().enable_junk_filter)
+}
+
+/// Turn the built-in junk name filter (uninstall, readme, …) on or off.
+/// The user's exclude_patterns apply either way.
+/// Cached scan results were filtered the old way, so they are dropped.
+#[tauri::command]
+async fn set_junk_filter(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    update_config(&app, "enable_junk_filter", |c| c.enable_junk_filter = enabled)?;
+    state.dir_cache.lock().unwrap().clear();
+    *state.store_apps.lock().unwrap() = None;
+    Ok(())
+}
+
+/// The user's blocklist: names containing any of these (case-insensitive) are
+/// left out of the index, whether or not the junk filter is on.
+#[tauri::command]
+async fn get_exclude_patterns(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.config.lock().unwrap().exclude_patterns.clone())
+}
+
+/// Replace the user's exclude list, e.g. ["setup", "debug"]. Blank patterns
+/// are ignored. Cached scans are dropped.
+#[tauri::command]
+async fn set_exclude_patterns(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    patterns: Vec<String>,
+) -> Result<(), String> {
+    let patterns: Vec<String> = patterns.into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    update_config(&app, "exclude_patterns", |c| c.exclude_patterns = patterns)?;
+    *state.store_apps.lock().unwrap() = None;
+    state.index.lock().unwrap().clear();
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_scan_depth(state: tauri::State<'_, AppState>) -> Result<usize, String> {
+    Ok(state.config.lock().unwrap().scan_depth)
+}
+
+/// Folder levels walked below each scan root (1–16, default 5). Returns the
+/// value actually saved.
+#[tauri::command]
+async fn set_scan_depth(app: AppHandle, state: tauri::State<'_, AppState>, depth: usize) -> Result<usize, String> {
+    let depth = depth.clamp(1, MAX_SCAN_DEPTH);
+    update_config(&app, "scan_depth", |c| c.scan_depth = depth)?;
+    state.index.lock().unwrap().clear();
+    Ok(depth)
+}
+
+const MAX_SCAN_THREADS: usize = 16;
+
+/// Effective scan parallelism (the configured value, or the adaptive default).
+#[tauri::command]
+async fn get_scan_threads(state: tauri::State<'_, AppState>) -> Result<usize, String> {
+    Ok(state.config.lock().unwrap().scan_threads.unwrap_or_else(default_scan_threads))
+}
 
-    if !out.status.success() { return None; }
-    let b64 = String::from_utf8_lossy(&out.stdout).trim().to_string();
-    if b64.is_empty() { return None; }
-    Some(b64)
+/// How many scan roots are walked in parallel. Fewer threads = slower scans but
+/// less CPU contention on weak machines; None restores the adaptive default.
+#[tauri::command]
+async fn set_scan_threads(app: AppHandle, threads: Option<usize>) -> Result<usize, String> {
+    let threads = threads.map(|n| n.clamp(1, MAX_SCAN_THREADS));
+    update_config(&app, "scan_threads", |c| c.scan_threads = threads)?;
+    Ok(threads.unwrap_or_else(default_scan_threads))
 }
 
-#[cfg(not(target_os = "windows"))]
-fn extract_icon_base64(_: &str) -> Option<String> { None }
+const UI_SCALE_MIN: f64 = 0.75;
+const UI_SCALE_MAX: f64 = 2.0;
 
-// ─── Tauri Commands ────────────────────────────────────────────────────────────
+/// The saved UI zoom factor. The frontend reads this on mount: nothing is
+/// listening yet when setup runs, so there's no startup `ui-scale-changed`.
+#[tauri::command]
+async fn get_ui_scale(state: tauri::State<'_, AppState>) -> Result<f64, String> {
+    Ok(state.config.lock().unwrap().ui_scale)
+}
 
+/// Persist the UI zoom factor (clamped to 0.75–2.0) and broadcast `ui-scale-changed`.
+/// Returns the value actually stored.
 #[tauri::command]
-async fn get_apps() -> Result<Vec<AppEntry>, String> {
-    let dirs = get_start_menu_dirs();
-    Ok(scan_apps(&dirs))
+async fn set_ui_scale(app: AppHandle, factor: f64) -> Result<f64, String> {
+    if !factor.is_finite() { return Err("invalid scale factor".into()); }
+    let scale = factor.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+    update_config(&app, "ui_scale", |c| c.ui_scale = scale)?;
+    let _ = app.emit("ui-scale-changed", scale);
+    Ok(scale)
 }
 
-/// Extract icon for a single app on demand (called per-item by frontend).
 #[tauri::command]
-async fn get_icon(path: String) -> Result<Option<String>, String> {
-    Ok(extract_icon_base64(&path))
+async fn get_allowlist(state: tauri::State<'_, AppState>) -> Result<Allowlist, String> {
+    let cfg = state.config.lock().unwrap();
+    Ok(Allowlist { enabled: cfg.allowlist_enabled, paths: cfg.allowlist.clone() })
 }
 
+/// Restrict listing and launching to `paths` (kiosk / managed setups).
+/// An empty list leaves everything allowed even when enabled.
 #[tauri::command]
-async fn launch_app(path: String) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    std::process::Command::new("cmd")
-        .args(["/C", "start", "", &path])
-        .spawn()
-        .map_err(|e| e.to_string())?;
+async fn set_allowlist(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    allowlist: Allowlist,
+) -> Result<(), String> {
+    ensure_unlocked(&state, "allowlist_enabled")?;
+    update_config(&app, "allowlist", |c| {
+        c.allowlist_enabled = allowlist.enabled;
+        c.allowlist = allowlist.paths;
+    })?;
+    state.index.lock().unwrap().clear();
     Ok(())
 }
 
+/// Whether a config field is pinned by machine policy (settings UI disables it).
 #[tauri::command]
-async fn hide_window(window: WebviewWindow) -> Result<(), String> {
-    window.hide().map_err(|e| e.to_string())
+async fn is_config_locked(field: String, state: tauri::State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.locked.lock().unwrap().contains(&field))
 }
 
 #[tauri::command]
-async fn show_window(window: WebviewWindow) -> Result<(), String> {
-    window.show().map_err(|e| e.to_string())?;
-    window.set_focus().map_err(|e| e.to_string())?;
+async fn validate_config(state: tauri::State<'_, AppState>) -> Result<Vec<ConfigIssue>, String> {
+    let index = state.index.lock().unwrap().clone();
+    let mut issues = config_issues(&state.config.lock().unwrap(), &index);
+    issues.extend(alias_issues(&state.aliases.lock().unwrap(), &index));
+    Ok(issues)
+}
+
+/// Prune dead references and clamp invalid values, returning what was fixed.
+#[tauri::command]
+async fn repair_config(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<Vec<ConfigIssue>, String> {
+    let index = state.index.lock().unwrap().clone();
+    let (mut fixed, config) = {
+        let locked = state.locked.lock().unwrap().clone();
+        let mut cfg = state.config.lock().unwrap();
+        let fixed = repair(&mut cfg, &locked, &index);
+        (fixed, cfg.clone())
+    };
+    if !fixed.is_empty() {
+        save_config(&app, &config)?;
+    }
+    let mut aliases = state.aliases.lock().unwrap();
+    let dead = alias_issues(&aliases, &index);
+    if !dead.is_empty() {
+        aliases.retain(|p, _| is_indexed(&index, p));
+        save_aliases(&app, &aliases)?;
+        fixed.extend(dead);
+    }
+    Ok(fixed)
+}
+
+/// All persisted settings (config and aliases) as one versioned JSON bundle.
+#[tauri::command]
+async fn export_settings(state: tauri::State<'_, AppState>) -> Result<String, String> {
+    let bundle = SettingsBundle {
+        schema: SETTINGS_SCHEMA,
+        config: state.config.lock().unwrap().clone(),
+        aliases: state.aliases.lock().unwrap().clone(),
+    };
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+/// Replace all settings with an `export_settings` bundle and apply them live:
+/// tray menu, backdrop, window size, always-on-top, UI scale, language and hotkey. Fields locked by machine
+/// policy keep their current values. Nothing is changed if the bundle is from
+/// another schema version or has invalid values.
+#[tauri::command]
+async fn import_settings(app: AppHandle, json: String) -> Result<(), String> {
+    let raw: serde_json::Value = serde_json::from_str(&json).map_err(|e| format!("not a settings bundle: {e}"))?;
+    let schema = raw.get("schema").and_then(|v| v.as_u64()).ok_or("not a settings bundle: missing schema version")?;
+    if schema != SETTINGS_SCHEMA {
+        return Err(format!("settings bundle uses schema v{schema}, this version only reads v{SETTINGS_SCHEMA}"));
+    }
+    let bundle: SettingsBundle = serde_json::from_value(raw).map_err(|e| format!("invalid settings bundle: {e}"))?;
+    // App references come from another install's index, so only values are checked
+    if let Some(i) = config_issues(&bundle.config, &[]).into_iter().find(|i| i.severity == Severity::Error) {
+        return Err(format!("invalid {} in settings bundle: {}", i.field, i.message));
+    }
+
+    let state = app.state::<AppState>();
+    let (previous, config) = {
+        let current = state.config.lock().unwrap().clone();
+        let to_map = |c: &Config| match serde_json::to_value(c) {
+            Ok(serde_json::Value::Object(m)) => Ok(m),
+            _ => Err("config did not serialize to an object".to_string()),
+        };
+        let (now, mut imported) = (to_map(&current)?, to_map(&bundle.config)?);
+        for field in state.locked.lock().unwrap().iter() {
+            if let Some(v) = now.get(field) { imported.insert(field.clone(), v.clone()); }
+        }
+        let config: Config = serde_json::from_value(serde_json::Value::Object(imported)).map_err(|e| e.to_string())?;
+        (current, config)
+    };
+    save_config(&app, &config)?;
+    save_aliases(&app, &bundle.aliases)?;
+    *state.config.lock().unwrap() = config.clone();
+    *state.aliases.lock().unwrap() = bundle.aliases;
+    // Pins, scan settings, commands and language all shape the index
+    state.index.lock().unwrap().clear();
+
+    rebuild_tray_menu(&app)?;
+    if let Some(window) = app.get_webview_window("main") {
+        apply_backdrop(&window, &config.backdrop);
+        window.set_always_on_top(config.always_on_top).map_err(|e| e.to_string())?;
+        if let Some(size) = config.window_size {
+            apply_window_size(&window, size)?;
+            center_window_on_screen(&window);
+        }
+    }
+    let _ = app.emit("ui-scale-changed", config.ui_scale);
+    let _ = app.emit("language-changed", config.language);
+    if let Some(accel) = config.hotkey.as_ref().filter(|&a| previous.hotkey.as_ref() != Some(a)) {
+        let shortcut: Shortcut = accel.parse().map_err(|e| format!("invalid hotkey {accel}: {e}"))?;
+        rebind_hotkey(&app, shortcut)
+            .map_err(|e| format!("settings imported, but hotkey {accel} is unavailable: {e}"))?;
+    }
     Ok(())
 }
 
-/// Save the current window position to shared state.
+/// Current native UI language ("zh" / "en"), for syncing the frontend's table.
 #[tauri::command]
-async fn save_window_pos(
-    window: WebviewWindow,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let pos = window.outer_position().map_err(|e| e.to_string())?;
-    *state.last_pos.lock().unwrap() = Some(WindowPos { x: pos.x, y: pos.y });
+async fn get_language(state: tauri::State<'_, AppState>) -> Result<Lang, String> {
+    Ok(state.config.lock().unwrap().language)
+}
+
+/// Switch native UI language: persists it, rebuilds the tray menu in place and
+/// emits `language-changed` so the frontend swaps its own table in sync.
+#[tauri::command]
+async fn set_language(app: AppHandle, lang: Lang) -> Result<(), String> {
+    update_config(&app, "language", |c| c.language = lang)?;
+    // System entry names are localized
+    app.state::<AppState>().index.lock().unwrap().clear();
+    rebuild_tray_menu(&app)?;
+    let _ = app.emit("language-changed", lang);
     Ok(())
 }
 
-/// Restore the last saved position, or center if none saved.
+/// Label of the bound toggle hotkey (e.g. "Ctrl+Shift+Space"), also while
+/// suspended. None if nothing is bound. For UIs that missed `hotkey-registered`.
 #[tauri::command]
-async fn restore_window_pos(
-    window: WebviewWindow,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let pos = state.last_pos.lock().unwrap().clone();
-    match pos {
-        Some(p) => window.set_position(tauri::PhysicalPosition::new(p.x, p.y))
-            .map_err(|e| e.to_string()),
-        None => { center_window_on_screen(&window); Ok(()) }
+async fn get_hotkey(state: tauri::State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state.hotkey.lock().unwrap().map(|s| format_shortcut(Some(s.mods), s.key)))
+}
+
+/// True when startup couldn't bind any hotkey (the `hotkey-failed` case) and
+/// none has been set since.
+#[tauri::command]
+async fn get_hotkey_failed(state: tauri::State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.hotkey_failed.load(Ordering::SeqCst))
+}
+
+/// Temporarily release the global hotkey (gaming, meetings).
+/// With `duration_secs` it comes back on its own; otherwise call `resume_hotkey`.
+#[tauri::command]
+async fn suspend_hotkey(app: AppHandle, duration_secs: Option<u64>) -> Result<(), String> {
+    suspend_hotkey_now(&app)?;
+    if let Some(secs) = duration_secs {
+        let epoch = app.state::<AppState>().hotkey_epoch.load(Ordering::SeqCst);
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
+            // A manual resume/suspend in the meantime supersedes this timer
+            if app.state::<AppState>().hotkey_epoch.load(Ordering::SeqCst) == epoch {
+                let _ = resume_hotkey_now(&app);
+            }
+        });
     }
+    Ok(())
+}
+
+#[tauri::command]
+async fn resume_hotkey(app: AppHandle) -> Result<(), String> {
+    resume_hotkey_now(&app)
+}
+
+/// Rebind the toggle hotkey, e.g. `(["ctrl", "alt"], "K")`. The old binding is
+/// kept if the new one is invalid or already taken. Persisted, and used instead
+/// of the default candidates from the next launch on. Returns the label.
+#[tauri::command]
+async fn set_hotkey(app: AppHandle, modifiers: Vec<String>, key: String) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    ensure_unlocked(&state, "hotkey")?;
+    let accel = hotkey_accelerator(&modifiers, &key);
+    let shortcut: Shortcut = accel.parse().map_err(|e| format!("invalid hotkey {accel}: {e}"))?;
+    rebind_hotkey(&app, shortcut).map_err(|e| format!("{accel} is unavailable: {e}"))?;
+    update_config(&app, "hotkey", |c| c.hotkey = Some(accel))?;
+    Ok(format_shortcut(Some(shortcut.mods), shortcut.key))
 }
 
 // ─── Window Helpers ────────────────────────────────────────────────────────────
 
-fn setup_window_blur<R: Runtime>(window: &WebviewWindow<R>) {
+// DWM_SYSTEMBACKDROP_TYPE values by setting name
+const BACKDROPS: &[(&str, u32)] = &[
+    ("none",    1), // DWMSBT_NONE
+    ("mica",    2), // DWMSBT_MAINWINDOW
+    ("acrylic", 3), // DWMSBT_TRANSIENTWINDOW
+    ("tabbed",  4), // DWMSBT_TABBEDWINDOW
+];
+
+fn backdrop_type(kind: &str) -> Option<u32> {
+    BACKDROPS.iter().find(|(k, _)| *k == kind).map(|&(_, v)| v)
+}
+
+/// Apply a system backdrop, falling back to acrylic and then none when the
+/// requested one is refused (DWMWA_SYSTEMBACKDROP_TYPE needs Windows 11 22H2).
+/// Returns the kind actually applied.
+fn apply_backdrop<R: Runtime>(window: &WebviewWindow<R>, kind: &str) -> &'static str {
     #[cfg(target_os = "windows")]
     {
         use windows::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_SYSTEMBACKDROP_TYPE};
         use windows::Win32::Foundation::HWND;
-        let hwnd = HWND(window.hwnd().unwrap().0);
-        let backdrop: u32 = 3; // DWMSBT_TRANSIENTWINDOW = Acrylic
-        unsafe {
-            let _ = DwmSetWindowAttribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE,
-                &backdrop as *const u32 as *const _, 4);
+        let Ok(raw) = window.hwnd() else { return "none" };
+        let hwnd = HWND(raw.0);
+        for candidate in [kind, "acrylic", "none"] {
+            let Some(&(name, value)) = BACKDROPS.iter().find(|(k, _)| *k == candidate) else { continue };
+            let applied = unsafe {
+                DwmSetWindowAttribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE,
+                    &value as *const u32 as *const _, 4)
+            };
+            if applied.is_ok() { return name; }
+        }
+        "none"
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (window, kind);
+        "none"
+    }
+}
+
+// Clicking the tray icon steals focus first; a toggle this soon after a
+// blur-hide means "close", not "reopen"
+const BLUR_TOGGLE_GRACE: Duration = Duration::from_millis(300);
+
+/// Hide the launcher whenever it loses focus, if `hide_on_blur` is on, and
+/// flush state to disk if the OS closes the window (e.g. at shutdown).
+fn setup_window_events<R: Runtime>(window: &WebviewWindow<R>) {
+    let win = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::Focused(false) => {
+            let state = win.state::<AppState>();
+            if state.config.lock().unwrap().hide_on_blur && launcher_visible(&win) {
+                *state.blur_hidden_at.lock().unwrap() = Some(Instant::now());
+                hide_with_fade(&win);
+            }
+        }
+        WindowEvent::CloseRequested { .. } => flush_state(win.app_handle()),
+        _ => {}
+    });
+}
+
+const DEFAULT_FADE_MS: u64 = 120;
+const MAX_FADE_MS: u64 = 1000;
+
+/// Visible and not already fading out.
+fn launcher_visible<R: Runtime>(window: &WebviewWindow<R>) -> bool {
+    window.is_visible().unwrap_or(false) && !window.state::<AppState>().hiding.load(Ordering::SeqCst)
+}
+
+/// Emit `window-will-hide` (payload: fade duration in ms) and hide once the
+/// frontend's fade-out has had `fade_ms` to play. Showing the window again in
+/// the meantime cancels the hide.
+fn hide_with_fade<R: Runtime>(window: &WebviewWindow<R>) {
+    let state = window.state::<AppState>();
+    let ms = state.config.lock().unwrap().fade_ms.min(MAX_FADE_MS);
+    let epoch = state.hide_epoch.load(Ordering::SeqCst);
+    let _ = window.emit("window-will-hide", ms);
+    if ms == 0 {
+        let _ = window.hide();
+        return;
+    }
+    state.hiding.store(true, Ordering::SeqCst);
+    let win = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        let state = win.state::<AppState>();
+        if state.hide_epoch.load(Ordering::SeqCst) == epoch {
+            let _ = win.hide();
+            state.hiding.store(false, Ordering::SeqCst);
         }
+    });
+}
+
+/// Call after show + focus: cancels a pending fade-out hide and emits
+/// `window-did-show` so the frontend can fade in.
+fn mark_shown<R: Runtime>(window: &WebviewWindow<R>) {
+    let state = window.state::<AppState>();
+    state.hide_epoch.fetch_add(1, Ordering::SeqCst);
+    state.hiding.store(false, Ordering::SeqCst);
+    let _ = window.emit("window-did-show", ());
+}
+
+/// Whether the window was hidden by focus loss within the grace period.
+/// Consumes the mark so only the first toggle is swallowed.
+fn just_hidden_on_blur<R: Runtime>(app: &AppHandle<R>) -> bool {
+    let state = app.state::<AppState>();
+    let at = state.blur_hidden_at.lock().unwrap().take();
+    at.is_some_and(|t| t.elapsed() < BLUR_TOGGLE_GRACE)
+}
+
+// (width, height) bounds for set_window_size, in logical pixels
+const MIN_WINDOW_SIZE: (u32, u32) = (400, 240);
+const MAX_WINDOW_SIZE: (u32, u32) = (2400, 1600);
+
+fn clamp_window_size(size: WindowSize) -> WindowSize {
+    WindowSize {
+        width: size.width.clamp(MIN_WINDOW_SIZE.0, MAX_WINDOW_SIZE.0),
+        height: size.height.clamp(MIN_WINDOW_SIZE.1, MAX_WINDOW_SIZE.1),
     }
 }
 
+fn apply_window_size<R: Runtime>(window: &WebviewWindow<R>, size: WindowSize) -> Result<(), String> {
+    window.set_size(tauri::LogicalSize::new(size.width, size.height)).map_err(|e| e.to_string())
+}
+
+/// Where the launcher sits on `monitor`: horizontally centered, slightly above middle.
+fn anchor_position(monitor: &tauri::Monitor, w: tauri::PhysicalSize<u32>) -> tauri::PhysicalPosition<i32> {
+    let (p, s) = (monitor.position(), monitor.size());
+    let x = p.x + (s.width as i32 - w.width as i32) / 2;
+    let y = p.y + (s.height as i32 / 2) - (w.height as i32 / 2) - 80;
+    tauri::PhysicalPosition::new(x, y)
+}
+
+/// Offset (x, y) from `monitor`'s top-left, clamped so a window of size `w`
+/// stays fully on it (the monitor may have shrunk since it was saved).
+fn position_within(monitor: &tauri::Monitor, w: tauri::PhysicalSize<u32>, x: i32, y: i32) -> tauri::PhysicalPosition<i32> {
+    let (p, s) = (monitor.position(), monitor.size());
+    let x = x.clamp(0, (s.width as i32 - w.width as i32).max(0));
+    let y = y.clamp(0, (s.height as i32 - w.height as i32).max(0));
+    tauri::PhysicalPosition::new(p.x + x, p.y + y)
+}
+
+/// Cursor position in physical screen pixels.
+#[cfg(target_os = "windows")]
+fn cursor_pos() -> Option<(i32, i32)> {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+    let mut pt = POINT::default();
+    unsafe { GetCursorPos(&mut pt) }.ok()?;
+    Some((pt.x, pt.y))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn cursor_pos() -> Option<(i32, i32)> { None }
+
+/// The monitor the user is on: the one under the cursor, else the primary.
+/// (current_monitor on a hidden window is wherever it was last shown.)
+fn active_monitor<R: Runtime>(window: &WebviewWindow<R>) -> Option<tauri::Monitor> {
+    let under_cursor = cursor_pos().and_then(|(x, y)| {
+        window.available_monitors().unwrap_or_default().into_iter().find(|m| monitor_contains(m, x, y))
+    });
+    under_cursor.or_else(|| window.primary_monitor().ok().flatten())
+}
+
 fn center_window_on_screen<R: Runtime>(window: &WebviewWindow<R>) {
-    if let Ok(Some(monitor)) = window.current_monitor() {
-        let s = monitor.size();
+    if let Some(monitor) = active_monitor(window) {
         let w = window.outer_size().unwrap_or_default();
-        let x = (s.width as i32 - w.width as i32) / 2;
-        let y = (s.height as i32 / 2) - (w.height as i32 / 2) - 80;
-        let _ = window.set_position(tauri::PhysicalPosition::new(x, y));
+        let _ = window.set_position(anchor_position(&monitor, w));
+    }
+}
+
+/// True if (x, y) lies on a connected monitor — the named one if given, else any.
+fn position_on_monitor<R: Runtime>(window: &WebviewWindow<R>, name: Option<&str>, x: i32, y: i32) -> bool {
+    window.available_monitors().unwrap_or_default().iter()
+        .filter(|m| name.is_none() || m.name().map(|n| n.as_str()) == name)
+        .any(|m| monitor_contains(m, x, y))
+}
+
+fn monitor_contains(m: &tauri::Monitor, x: i32, y: i32) -> bool {
+    let (p, s) = (m.position(), m.size());
+    x >= p.x && y >= p.y && x < p.x + s.width as i32 && y < p.y + s.height as i32
+}
+
+// ─── Updates ───────────────────────────────────────────────────────────────────
+
+const RELEASES_API: &str = "https://api.github.com/repos/Christinaper/quicklaunch/releases/latest";
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// "v1.10.0" → (1, 10, 0); missing parts count as 0 ("1.0" = "1.0.0") and
+/// pre-release / build suffixes are ignored.
+fn version_parts(v: &str) -> (u64, u64, u64) {
+    let mut parts = v.trim().trim_start_matches(['v', 'V'])
+        .split(['-', '+']).next().unwrap_or("")
+        .split('.')
+        .map(|p| p.parse().unwrap_or(0));
+    let mut next = || parts.next().unwrap_or(0);
+    (next(), next(), next())
+}
+
+/// Compare this build against the latest GitHub release.
+#[tauri::command]
+async fn check_for_update() -> Result<UpdateInfo, String> {
+    #[derive(Deserialize)]
+    struct Release { tag_name: String, html_url: String }
+
+    let client = reqwest::Client::builder()
+        .timeout(UPDATE_TIMEOUT)
+        .user_agent(concat!("QuickLaunch/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let resp = client.get(RELEASES_API)
+        .header("Accept", "application/vnd.github+json")
+        .send().await
+        .map_err(|e| if e.is_timeout() || e.is_connect() {
+            "can't reach GitHub (offline?)".to_string()
+        } else {
+            format!("update check failed: {e}")
+        })?;
+    match resp.status().as_u16() {
+        200 => {}
+        403 | 429 => return Err("GitHub rate limit reached, try again later".into()),
+        404 => return Err("no releases published yet".into()),
+        code => return Err(format!("update check failed: HTTP {code}")),
+    }
+    let release: Release = resp.json().await.map_err(|e| format!("unexpected release data: {e}"))?;
+
+    let current = env!("CARGO_PKG_VERSION").to_string();
+    let latest = release.tag_name.trim_start_matches(['v', 'V']).to_string();
+    Ok(UpdateInfo {
+        update_available: version_parts(&latest) > version_parts(&current),
+        current,
+        latest,
+        url: release.html_url,
+    })
+}
+
+// ─── Autostart ─────────────────────────────────────────────────────────────────
+
+const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+const RUN_VALUE: &str = "QuickLaunch";
+// Passed by the Run entry so a boot-time start stays in the tray
+const HIDDEN_FLAG: &str = "--hidden";
+
+#[cfg(target_os = "windows")]
+fn read_autostart() -> bool {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+    RegKey::predef(HKEY_CURRENT_USER).open_subkey(RUN_KEY)
+        .and_then(|k| k.get_value::<String, _>(RUN_VALUE))
+        .is_ok()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_autostart() -> bool { false }
+
+#[cfg(target_os = "windows")]
+fn write_autostart(enabled: bool) -> Result<(), String> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_SET_VALUE};
+    use winreg::RegKey;
+    let key = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey_with_flags(RUN_KEY, KEY_SET_VALUE)
+        .map_err(|e| format!("cannot open Run key: {e}"))?;
+    if enabled {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let command = format!("\"{}\" {HIDDEN_FLAG}", exe.display());
+        key.set_value(RUN_VALUE, &command).map_err(|e| format!("cannot write Run entry: {e}"))
+    } else {
+        match key.delete_value(RUN_VALUE) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("cannot remove Run entry: {e}")),
+            _ => Ok(()),
+        }
     }
 }
 
+#[cfg(not(target_os = "windows"))]
+fn write_autostart(_enabled: bool) -> Result<(), String> {
+    Err("start on boot is only supported on Windows".into())
+}
+
+/// Whether QuickLaunch starts with Windows (HKCU Run entry present).
+#[tauri::command]
+async fn get_autostart() -> Result<bool, String> {
+    Ok(read_autostart())
+}
+
+/// Add or remove the HKCU Run entry that starts QuickLaunch hidden at login.
+#[tauri::command]
+async fn set_autostart(enabled: bool) -> Result<(), String> {
+    write_autostart(enabled)
+}
+
 // ─── Tray ──────────────────────────────────────────────────────────────────────
 
+const TRAY_ID: &str = "main";
+
+/// Tray menu in the configured language; also records the hotkey item for later relabeling.
+fn tray_menu<M: Manager<tauri::Wry>>(app: &M) -> tauri::Result<Menu<tauri::Wry>> {
+    let state = app.state::<AppState>();
+    let t = current_strings(&state);
+    let hotkey_text = if state.hotkey_suspended.load(Ordering::SeqCst) {
+        t.tray_resume_hotkey
+    } else {
+        t.tray_pause_hotkey
+    };
+    let show_item   = MenuItem::with_id(app, "show",   t.tray_show,   true, None::<&str>)?;
+    let hotkey_item = MenuItem::with_id(app, "hotkey", hotkey_text,   true, None::<&str>)?;
+    let quit_item   = MenuItem::with_id(app, "quit",   t.tray_quit,   true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show_item, &hotkey_item, &quit_item])?;
+    *state.hotkey_item.lock().unwrap() = Some(hotkey_item);
+    Ok(menu)
+}
+
+/// Swap in a fresh tray menu, e.g. after the language changed.
+fn rebuild_tray_menu(app: &AppHandle) -> Result<(), String> {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let menu = tray_menu(app).map_err(|e| e.to_string())?;
+        tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
 fn build_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
-    let show_item = MenuItem::with_id(app, "show", "打开 QuickLaunch", true, None::<&str>)?;
-    let quit_item = MenuItem::with_id(app, "quit", "退出",             true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show_item, &quit_item])?;
+    let menu = tray_menu(app)?;
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().unwrap().clone())
         .tooltip("QuickLaunch")
         .menu(&menu)
         .on_menu_event(|app: &AppHandle, event| {
             match event.id.as_ref() {
-                "show" => {
-                    if let Some(win) = app.get_webview_window("main") {
-                        center_window_on_screen(&win);
-                        let _ = win.show();
-                        let _ = win.set_focus();
-                        let _ = win.emit("reset-search", ());
-                    }
+                "show" => summon_main_window(app),
+                "hotkey" => {
+                    let suspended = app.state::<AppState>().hotkey_suspended.load(Ordering::SeqCst);
+                    let _ = if suspended { resume_hotkey_now(app) } else { suspend_hotkey_now(app) };
                 }
-                "quit" => app.exit(0),
+                "quit" => quit(app),
                 _ => {}
             }
         })
         .on_tray_icon_event(|tray, event| {
             // Left-click tray icon → toggle window
             if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
-                let app = tray.app_handle();
-                if let Some(win) = app.get_webview_window("main") {
-                    if win.is_visible().unwrap_or(false) {
-                        let _ = win.hide();
-                    } else {
-                        center_window_on_screen(&win);
-                        let _ = win.show();
-                        let _ = win.set_focus();
-                        let _ = win.emit("reset-search", ());
-                    }
-                }
+                toggle_main_window(tray.app_handle());
             }
         })
         .build(app)?;
@@ -248,31 +4383,63 @@ const HOTKEY_CANDIDATES: &[(Option<Modifiers>, Code)] = &[
     (Some(Modifiers::CONTROL.union(Modifiers::SHIFT)), Code::KeyQ),
 ];
 
+/// Center, show and focus the launcher with a fresh search.
+fn summon_main_window(app: &AppHandle) {
+    if let Some(win) = app.get_webview_window("main") {
+        let _ = win.set_ignore_cursor_events(false);
+        center_window_on_screen(&win);
+        let _ = win.show();
+        let _ = win.set_focus();
+        mark_shown(&win);
+        let _ = win.emit("reset-search", ());
+    }
+}
+
+fn toggle_main_window(app: &AppHandle) {
+    if let Some(win) = app.get_webview_window("main") {
+        if launcher_visible(&win) {
+            hide_with_fade(&win);
+        } else if !just_hidden_on_blur(app) {
+            summon_main_window(app);
+        }
+    }
+}
+
+/// Register `shortcut` as the window toggle.
+fn bind_hotkey(app: &AppHandle, shortcut: Shortcut) -> Result<(), String> {
+    app.global_shortcut().on_shortcut(shortcut, |app, _shortcut, event| {
+        use tauri_plugin_global_shortcut::ShortcutState;
+        if event.state() != ShortcutState::Pressed { return; }
+        let h = app.clone();
+        tauri::async_runtime::spawn(async move { toggle_main_window(&h) });
+    }).map_err(|e| e.to_string())
+}
+
+/// "ctrl", "win", "K" → "ctrl+super+K", in the syntax `Shortcut` parses.
+fn hotkey_accelerator(modifiers: &[String], key: &str) -> String {
+    modifiers.iter()
+        .map(|m| match m.trim().to_lowercase().as_str() {
+            "win" | "windows" | "meta" => "super".to_string(),
+            other => other.to_string(),
+        })
+        .chain(std::iter::once(key.trim().to_string()))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Bind the saved hotkey, or the first free default when none is saved.
 fn register_hotkey(app: &mut App) {
     let handle = app.handle().clone();
-    for &(mods, key) in HOTKEY_CANDIDATES {
-        let shortcut = Shortcut::new(mods, key);
-        let handle_clone = handle.clone();
-        let result = app.global_shortcut().on_shortcut(shortcut, move |_app, _shortcut, event| {
-            use tauri_plugin_global_shortcut::ShortcutState;
-            if event.state() != ShortcutState::Pressed { return; }
-            let h = handle_clone.clone();
-            tauri::async_runtime::spawn(async move {
-                if let Some(win) = h.get_webview_window("main") {
-                    if win.is_visible().unwrap_or(false) {
-                        let _ = win.hide();
-                    } else {
-                        center_window_on_screen(&win);
-                        let _ = win.show();
-                        let _ = win.set_focus();
-                        let _ = win.emit("reset-search", ());
-                    }
-                }
-            });
-        });
-        match result {
-            Ok(_) => {
-                let label = format_shortcut(mods, key);
+    let saved = handle.state::<AppState>().config.lock().unwrap().hotkey.clone();
+    let candidates: Vec<Shortcut> = match saved.and_then(|a| a.parse().ok()) {
+        Some(shortcut) => vec![shortcut],
+        None => HOTKEY_CANDIDATES.iter().map(|&(mods, key)| Shortcut::new(mods, key)).collect(),
+    };
+    for shortcut in candidates {
+        match bind_hotkey(&handle, shortcut) {
+            Ok(()) => {
+                *handle.state::<AppState>().hotkey.lock().unwrap() = Some(shortcut);
+                let label = format_shortcut(Some(shortcut.mods), shortcut.key);
                 eprintln!("[QuickLaunch] Hotkey registered: {label}");
                 let _ = handle.emit("hotkey-registered", label);
                 return;
@@ -281,53 +4448,584 @@ fn register_hotkey(app: &mut App) {
         }
     }
     eprintln!("[QuickLaunch] No global hotkey registered.");
-    let _ = handle.emit("hotkey-failed", "所有热键均被占用，请通过系统托盘图标打开启动器");
+    handle.state::<AppState>().hotkey_failed.store(true, Ordering::SeqCst);
+    let t = current_strings(&handle.state::<AppState>());
+    let _ = handle.emit("hotkey-failed", t.hotkey_failed);
+}
+
+/// Swap the toggle hotkey for `shortcut`, restoring the old binding if the new
+/// one can't be registered. Emits `hotkey-registered` on success.
+fn rebind_hotkey(app: &AppHandle, shortcut: Shortcut) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    // Currently registered shortcut (a suspended one isn't)
+    let bound = state.hotkey.lock().unwrap().filter(|_| !state.hotkey_suspended.load(Ordering::SeqCst));
+    if let Some(old) = bound {
+        app.global_shortcut().unregister(old).map_err(|e| e.to_string())?;
+    }
+    if let Err(e) = bind_hotkey(app, shortcut) {
+        if let Some(old) = bound {
+            let _ = bind_hotkey(app, old);
+        }
+        return Err(e);
+    }
+
+    *state.hotkey.lock().unwrap() = Some(shortcut);
+    state.hotkey_epoch.fetch_add(1, Ordering::SeqCst);
+    state.hotkey_suspended.store(false, Ordering::SeqCst);
+    state.hotkey_failed.store(false, Ordering::SeqCst);
+    refresh_hotkey_menu_text(&state);
+    let _ = app.emit("hotkey-registered", format_shortcut(Some(shortcut.mods), shortcut.key));
+    Ok(())
+}
+
+/// Label the tray's hotkey item for the current suspended state and language.
+fn refresh_hotkey_menu_text(state: &AppState) {
+    let t = current_strings(state);
+    let text = if state.hotkey_suspended.load(Ordering::SeqCst) {
+        t.tray_resume_hotkey
+    } else {
+        t.tray_pause_hotkey
+    };
+    if let Some(item) = state.hotkey_item.lock().unwrap().as_ref() {
+        let _ = item.set_text(text);
+    }
+}
+
+/// Unregister the toggle hotkey until `resume_hotkey_now`. No-op if already suspended.
+fn suspend_hotkey_now(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.hotkey_epoch.fetch_add(1, Ordering::SeqCst);
+    if state.hotkey_suspended.load(Ordering::SeqCst) { return Ok(()); }
+    let shortcut = (*state.hotkey.lock().unwrap()).ok_or("no global hotkey is registered")?;
+    app.global_shortcut().unregister(shortcut).map_err(|e| e.to_string())?;
+    state.hotkey_suspended.store(true, Ordering::SeqCst);
+    refresh_hotkey_menu_text(&state);
+    let _ = app.emit("hotkey-suspended", ());
+    Ok(())
 }
 
+/// Re-register the suspended toggle hotkey. No-op if it isn't suspended.
+fn resume_hotkey_now(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.hotkey_epoch.fetch_add(1, Ordering::SeqCst);
+    if !state.hotkey_suspended.load(Ordering::SeqCst) { return Ok(()); }
+    let shortcut = (*state.hotkey.lock().unwrap()).ok_or("no global hotkey is registered")?;
+    bind_hotkey(app, shortcut)?;
+    state.hotkey_suspended.store(false, Ordering::SeqCst);
+    refresh_hotkey_menu_text(&state);
+    let _ = app.emit("hotkey-resumed", format_shortcut(Some(shortcut.mods), shortcut.key));
+    Ok(())
+}
+
+/// Readable label for a key as printed on the keyboard: KeyA → "A",
+/// Digit1 → "1", ArrowUp → "Up", Minus → "-". Keys without a special case
+/// (F1–F24, Home, PageUp, …) use their `Code` name.
+fn key_label(key: Code) -> String {
+    let named = match key {
+        Code::ArrowUp      => "Up",
+        Code::ArrowDown    => "Down",
+        Code::ArrowLeft    => "Left",
+        Code::ArrowRight   => "Right",
+        Code::Escape       => "Esc",
+        Code::Delete       => "Del",
+        Code::Insert       => "Ins",
+        Code::PageUp       => "PgUp",
+        Code::PageDown     => "PgDn",
+        Code::PrintScreen  => "PrtSc",
+        Code::Minus        => "-",
+        Code::Equal        => "=",
+        Code::BracketLeft  => "[",
+        Code::BracketRight => "]",
+        Code::Backslash    => "\\",
+        Code::Semicolon    => ";",
+        Code::Quote        => "'",
+        Code::Backquote    => "`",
+        Code::Comma        => ",",
+        Code::Period       => ".",
+        Code::Slash        => "/",
+        Code::NumpadAdd      => "Num+",
+        Code::NumpadSubtract => "Num-",
+        Code::NumpadMultiply => "Num*",
+        Code::NumpadDivide   => "Num/",
+        Code::NumpadDecimal  => "Num.",
+        Code::NumpadEnter    => "NumEnter",
+        Code::AudioVolumeUp   => "Volume Up",
+        Code::AudioVolumeDown => "Volume Down",
+        Code::AudioVolumeMute => "Mute",
+        Code::MediaPlayPause     => "Play/Pause",
+        Code::MediaTrackNext     => "Next Track",
+        Code::MediaTrackPrevious => "Previous Track",
+        _ => "",
+    };
+    if !named.is_empty() { return named.to_string(); }
+    let name = key.to_string();
+    for prefix in ["Key", "Digit"] {
+        if let Some(rest) = name.strip_prefix(prefix) { return rest.to_string(); }
+    }
+    if let Some(rest) = name.strip_prefix("Numpad") { return format!("Num{rest}"); }
+    name
+}
+
+/// "Win+Ctrl+Alt+Shift+K": modifiers in Windows' own order, then the key.
 fn format_shortcut(mods: Option<Modifiers>, key: Code) -> String {
     let mut parts = Vec::new();
     if let Some(m) = mods {
-        if m.contains(Modifiers::CONTROL) { parts.push("Ctrl"); }
-        if m.contains(Modifiers::ALT)     { parts.push("Alt"); }
-        if m.contains(Modifiers::SHIFT)   { parts.push("Shift"); }
-        if m.contains(Modifiers::SUPER)   { parts.push("Win"); }
-    }
-    parts.push(match key {
-        Code::Space => "Space",
-        Code::F1    => "F1",
-        Code::KeyQ  => "Q",
-        _           => "?",
-    });
+        if m.contains(Modifiers::SUPER)   { parts.push("Win".to_string()); }
+        if m.contains(Modifiers::CONTROL) { parts.push("Ctrl".to_string()); }
+        if m.contains(Modifiers::ALT)     { parts.push("Alt".to_string()); }
+        if m.contains(Modifiers::SHIFT)   { parts.push("Shift".to_string()); }
+    }
+    parts.push(key_label(key));
     parts.join("+")
 }
 
 fn setup(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    let (config, locked) = load_config(app.handle());
+    let state = app.state::<AppState>();
+    *state.config.lock().unwrap() = config;
+    *state.locked.lock().unwrap() = locked;
+    *state.last_pos.lock().unwrap() = load_window_pos(app.handle());
+    *state.usage.lock().unwrap() = load_usage(app.handle());
+    *state.aliases.lock().unwrap() = load_aliases(app.handle());
+    load_app_cache(app.handle(), &state);
+
     let window = app.get_webview_window("main").unwrap();
-    setup_window_blur(&window);
+    let backdrop = state.config.lock().unwrap().backdrop.clone();
+    apply_backdrop(&window, &backdrop);
+    let (size, on_top) = {
+        let cfg = state.config.lock().unwrap();
+        (cfg.window_size, cfg.always_on_top)
+    };
+    let _ = window.set_always_on_top(on_top);
+    if let Some(size) = size {
+        if let Err(e) = apply_window_size(&window, clamp_window_size(size)) {
+            eprintln!("[QuickLaunch] Couldn't restore window size: {e}");
+        }
+    }
+    setup_window_events(&window);
     center_window_on_screen(&window);
     let _ = window.hide();
     build_tray(app)?;
     register_hotkey(app);
+    match start_watcher(app.handle()) {
+        Ok(w) => *app.state::<AppState>().watcher.lock().unwrap() = Some(w),
+        Err(e) => eprintln!("[QuickLaunch] Start Menu watcher unavailable: {e}"),
+    }
+    // Started by hand: show the launcher; started at login: stay in the tray
+    if !std::env::args().any(|a| a == HIDDEN_FLAG) {
+        summon_main_window(app.handle());
+    }
     Ok(())
 }
 
 // ─── Entry Point ───────────────────────────────────────────────────────────────
 
-pub fn run() {
-    tauri::Builder::default()
-        .manage(AppState { last_pos: Mutex::new(None) })
-        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .plugin(tauri_plugin_shell::init())
-        .setup(setup)
-        .invoke_handler(tauri::generate_handler![
+/// `generate_handler!` for every command, plus `extra` ones (debug builds).
+macro_rules! app_commands {
+    ($($extra:ident),* $(,)?) => {
+        tauri::generate_handler![
             get_apps,
+            get_apps_ranked,
+            force_full_scan,
+            scan_folder_once,
+            get_recently_installed,
+            get_recent,
+            get_categories,
+            set_category_hidden,
+            search_apps,
+            set_alias,
+            remove_alias,
+            resolve_best,
+            evaluate_expression,
+            resolve_targets,
+            get_apps_by_drive,
+            get_apps_by_size,
+            open_app_data_dir,
+            reveal_in_explorer,
+            open_app_url,
             get_icon,
+            get_icons,
+            set_custom_icon,
+            clear_custom_icon,
+            get_icon_accent,
+            get_icon_stats,
             launch_app,
+            launch_app_elevated,
+            get_app_details,
+            copy_path,
+            launch_and_notify,
             hide_window,
             show_window,
+            show_with_selection,
             save_window_pos,
             restore_window_pos,
-        ])
+            set_click_through,
+            get_window_state,
+            apply_window_state,
+            get_ui_scale,
+            set_ui_scale,
+            get_hotkey,
+            get_hotkey_failed,
+            suspend_hotkey,
+            resume_hotkey,
+            set_hotkey,
+            get_portableapps_root,
+            set_portableapps_root,
+            set_backdrop,
+            get_fade_ms,
+            set_fade_ms,
+            set_launch_error_toast,
+            get_window_size,
+            set_window_size,
+            set_always_on_top,
+            check_for_update,
+            export_settings,
+            import_settings,
+            get_autostart,
+            set_autostart,
+            get_hide_on_blur,
+            set_hide_on_blur,
+            add_command_entry,
+            remove_command_entry,
+            get_pinned,
+            pin_app,
+            unpin_app,
+            reorder_pins,
+            set_skip_slow_drives,
+            last_scan_info,
+            get_scan_dirs,
+            set_scan_dirs,
+            get_junk_filter,
+            set_junk_filter,
+            get_exclude_patterns,
+            set_exclude_patterns,
+            get_scan_depth,
+            set_scan_depth,
+            get_scan_threads,
+            set_scan_threads,
+            get_allowlist,
+            set_allowlist,
+            is_config_locked,
+            validate_config,
+            repair_config,
+            get_language,
+            set_language,
+            $($extra,)*
+        ]
+    };
+}
+
+pub fn run() {
+    let builder = tauri::Builder::default()
+        .manage(AppState {
+            last_pos: Mutex::new(None),
+            accents:  Mutex::new(HashMap::new()),
+            index:    Mutex::new(Vec::new()),
+            dir_cache: Mutex::new(HashMap::new()),
+            config:   Mutex::new(Config::default()),
+            waiters:  AtomicUsize::new(0),
+            icon_counts: IconCounters::default(),
+            hotkey:   Mutex::new(None),
+            hotkey_suspended: AtomicBool::new(false),
+            hotkey_failed: AtomicBool::new(false),
+            hotkey_epoch: AtomicUsize::new(0),
+            hotkey_item: Mutex::new(None),
+            targets:  Mutex::new(HashMap::new()),
+            locked:   Mutex::new(HashSet::new()),
+            sizes:    Mutex::new(HashMap::new()),
+            sizing:   AtomicBool::new(false),
+            usage:    Mutex::new(HashMap::new()),
+            aliases:  Mutex::new(HashMap::new()),
+            blur_hidden_at: Mutex::new(None),
+            skipped_dirs: Mutex::new(Vec::new()),
+            scanned_at: Mutex::new(None),
+            from_cache: AtomicBool::new(false),
+            hide_epoch: AtomicUsize::new(0),
+            hiding:   AtomicBool::new(false),
+            store_apps: Mutex::new(None),
+            watcher:  Mutex::new(None),
+            rescanning: AtomicBool::new(false),
+        })
+        // Must come first: a second launch hands off here and exits before its
+        // own setup runs, so it never builds a tray or registers the hotkey
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| summon_main_window(app)))
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .setup(setup);
+    // QA-only commands aren't even compiled into release builds
+    #[cfg(debug_assertions)]
+    let builder = builder.invoke_handler(app_commands![cycle_monitors]);
+    #[cfg(not(debug_assertions))]
+    let builder = builder.invoke_handler(app_commands![]);
+    builder
         .run(tauri::generate_context!())
         .expect("error while running QuickLaunch");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_state_round_trips_through_json() {
+        for monitor in [Some("\\\\.\\DISPLAY2".to_string()), None] {
+            for pinned in [true, false] {
+                let state = WindowState {
+                    x: -1280, y: 40, width: 720, height: 480,
+                    monitor: monitor.clone(), visible: true, always_on_top: false, pinned,
+                };
+                let json = serde_json::to_value(&state).unwrap();
+                let back: WindowState = serde_json::from_value(json.clone()).unwrap();
+                assert_eq!(back.pinned, pinned);
+                assert_eq!(serde_json::to_value(&back).unwrap(), json);
+            }
+        }
+    }
+
+    /// Fresh empty directory under the system temp dir, unique to this test run.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("quicklaunch-test-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rescan_walks_only_changed_roots() {
+        let rules = ScanRules { depth: DEFAULT_SCAN_DEPTH, exclude: Vec::new() };
+        let (a, b) = (temp_dir("scan-a"), temp_dir("scan-b"));
+        std::fs::write(a.join("Alpha.exe"), b"").unwrap();
+        std::fs::write(b.join("Beta.exe"), b"").unwrap();
+
+        let (hash_a, walked) = rescan_root(&a, &rules, true, None, &|_| {});
+        assert_eq!(walked.unwrap().len(), 1);
+        let (hash_b, walked) = rescan_root(&b, &rules, true, None, &|_| {});
+        assert_eq!(walked.unwrap().len(), 1);
+
+        std::fs::create_dir(b.join("Tools")).unwrap();
+        std::fs::write(b.join("Tools").join("Gamma.exe"), b"").unwrap();
+
+        let (_, walked) = rescan_root(&a, &rules, true, Some(hash_a), &|_| {});
+        assert!(walked.is_none(), "unchanged root was walked again");
+        let (_, walked) = rescan_root(&b, &rules, true, Some(hash_b), &|_| {});
+        let mut names: Vec<String> = walked.expect("changed root was not walked").into_iter().map(|e| e.name).collect();
+        names.sort();
+        assert_eq!(names, ["Beta", "Gamma"]);
+
+        let _ = std::fs::remove_dir_all(&a);
+        let _ = std::fs::remove_dir_all(&b);
+    }
+
+    #[test]
+    fn webp_icons_are_smaller_than_png() {
+        // Jumbo-sized icon: shaded disc on a transparent background
+        let icon = image::RgbaImage::from_fn(256, 256, |x, y| {
+            let (dx, dy) = (x as f32 - 127.5, y as f32 - 127.5);
+            if dx * dx + dy * dy > 120.0 * 120.0 { return image::Rgba([0, 0, 0, 0]); }
+            image::Rgba([30 + (y / 4) as u8, 120 + (x / 8) as u8, 215, 255])
+        });
+        let png = encode_png(&icon).unwrap();
+        let webp = png_to_webp(&png).expect("WebP encode failed");
+        assert!(webp.len() < png.len(), "WebP {} bytes vs PNG {} bytes", webp.len(), png.len());
+    }
+
+    #[test]
+    fn ampersand_paths_launch_verbatim() {
+        // No shortcut: the path itself, run from its own folder
+        let (file, args, dir) = resolve_launch(ShortcutInfo::default(), "C:/Tools/R&D/Build & Run.exe", None, None);
+        assert_eq!(file, "C:/Tools/R&D/Build & Run.exe");
+        assert_eq!(args, None);
+        assert_eq!(dir.as_deref(), Some("C:/Tools/R&D"));
+
+        // Shortcut whose target, arguments and folder all contain '&'
+        let rnd = temp_dir("R&D");
+        let target = rnd.join("Build & Run.exe");
+        std::fs::write(&target, b"").unwrap();
+        let target = target.to_string_lossy().to_string();
+        let link = ShortcutInfo {
+            target: Some(target.clone()),
+            args: Some("--out \"a&b.txt\" & echo".to_string()),
+            working_dir: Some(rnd.to_string_lossy().to_string()),
+        };
+        let (file, args, dir) = resolve_launch(link, "C:/Users/me/Desktop/Build & Run.lnk", None, None);
+        assert_eq!(file, target);
+        assert_eq!(args.as_deref(), Some("--out \"a&b.txt\" & echo"));
+        assert_eq!(dir, Some(rnd.to_string_lossy().to_string()));
+        let _ = std::fs::remove_dir_all(&rnd);
+    }
+
+    #[test]
+    fn shortcuts_format_in_windows_order() {
+        let all = Modifiers::SHIFT | Modifiers::SUPER | Modifiers::CONTROL | Modifiers::ALT;
+        assert_eq!(format_shortcut(Some(all), Code::KeyK), "Win+Ctrl+Alt+Shift+K");
+        assert_eq!(format_shortcut(Some(Modifiers::ALT | Modifiers::CONTROL), Code::Space), "Ctrl+Alt+Space");
+        assert_eq!(format_shortcut(None, Code::F1), "F1");
+        assert_eq!(key_label(Code::KeyA), "A");
+        assert_eq!(key_label(Code::Digit1), "1");
+        assert_eq!(key_label(Code::ArrowUp), "Up");
+        assert_eq!(key_label(Code::Numpad5), "Num5");
+        assert_eq!(key_label(Code::NumpadAdd), "Num+");
+        assert_eq!(key_label(Code::Backquote), "`");
+        assert_eq!(key_label(Code::F5), "F5");
+    }
+
+    #[test]
+    fn finish_index_keeps_the_working_duplicate() {
+        let lnk = |path: &str, target: &str, broken: bool| AppEntry {
+            name: "Editor".to_string(),
+            path: path.to_string(),
+            target: Some(target.to_string()),
+            broken,
+            ..Default::default()
+        };
+        let broken = lnk("C:/ProgramData/Start Menu/Editor.lnk", "C:/Old/editor.exe", true);
+        let working = lnk("C:/Users/me/Start Menu/Editor.lnk", "C:/New/editor.exe", false);
+        for apps in [vec![broken.clone(), working.clone()], vec![working.clone(), broken.clone()]] {
+            let kept = finish_index(apps);
+            assert_eq!(kept.len(), 1);
+            assert_eq!(kept[0].path, working.path);
+        }
+
+        // An unreadable shortcut loses to a working one but beats a broken one
+        let unread = AppEntry { target: None, broken: false, ..broken.clone() };
+        assert_eq!(finish_index(vec![unread.clone(), working.clone()])[0].path, working.path);
+        assert_eq!(finish_index(vec![broken.clone(), unread.clone()])[0].target, None);
+    }
+
+    #[test]
+    fn uninstalled_targets_are_marked_broken() {
+        let dir = temp_dir("uninstalled");
+        let target = dir.join("editor.exe");
+        std::fs::write(&target, b"").unwrap();
+        let entry = AppEntry {
+            name: "Editor".to_string(),
+            path: "C:/Users/me/Start Menu/Editor.lnk".to_string(),
+            target: Some(target.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        assert!(!finish_index(vec![entry.clone()])[0].broken);
+        std::fs::remove_file(&target).unwrap();
+        // Same cached entry, still flagged as working from when its root was walked
+        assert!(finish_index(vec![entry])[0].broken);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn indexed(path: &str, category: &str) -> AppEntry {
+        AppEntry { name: path.to_string(), path: path.to_string(), category: category.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn repair_drops_pins_to_missing_apps() {
+        let index = [indexed("C:/Apps/Editor.lnk", "Programs")];
+        let mut cfg = Config {
+            pins: vec!["C:/Apps/Gone.lnk".to_string(), "C:/Apps/Editor.lnk".to_string()],
+            ..Default::default()
+        };
+        assert!(config_issues(&cfg, &[]).iter().all(|i| i.field != "pins"));
+        let fixed = repair(&mut cfg, &HashSet::new(), &index);
+        assert_eq!(fixed.iter().filter(|i| i.field == "pins").count(), 1);
+        assert_eq!(cfg.pins, ["C:/Apps/Editor.lnk"]);
+    }
+
+    #[test]
+    fn repair_drops_unknown_hidden_categories() {
+        let index = [indexed("C:/Apps/Editor.lnk", "Programs")];
+        let mut cfg = Config {
+            hidden_categories: vec!["Programs".to_string(), "Games".to_string()],
+            ..Default::default()
+        };
+        let fixed = repair(&mut cfg, &HashSet::new(), &index);
+        assert_eq!(fixed.iter().filter(|i| i.field == "hidden_categories").count(), 1);
+        assert_eq!(cfg.hidden_categories, ["Programs"]);
+    }
+
+    #[test]
+    fn aliases_for_missing_apps_are_reported() {
+        let index = [indexed("C:/Apps/Editor.lnk", "Programs")];
+        let aliases = HashMap::from([
+            ("C:/Apps/Editor.lnk".to_string(), "ed".to_string()),
+            ("C:/Apps/Gone.lnk".to_string(), "gone".to_string()),
+        ]);
+        assert!(alias_issues(&aliases, &[]).is_empty());
+        let issues = alias_issues(&aliases, &index);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "aliases");
+        assert!(issues[0].message.ends_with("C:/Apps/Gone.lnk"));
+    }
+
+    #[test]
+    fn clickonce_apps_index_as_themselves() {
+        let dir = temp_dir("clickonce");
+        let file = dir.join("Contoso App.appref-ms");
+        std::fs::write(&file, "https://apps.contoso.com/app.application#App.application, Culture=neutral").unwrap();
+        let rules = ScanRules { depth: DEFAULT_SCAN_DEPTH, exclude: Vec::new() };
+        let apps = scan_dir(&dir, &rules, false, &|_| {});
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].name, "Contoso App");
+        assert_eq!(apps[0].path, file.to_string_lossy());
+        assert_eq!(apps[0].target, None);
+        assert!(!apps[0].broken);
+
+        // Launched as the file itself (ShellExecute hands it to dfshim), never via `cmd /C start`:
+        // not a virtual entry, so open_entry sends it down launch_entry
+        let a = &apps[0];
+        assert!(!is_virtual_entry(&a.path));
+        let link = ShortcutInfo { target: a.target.clone(), args: a.args.clone(), working_dir: a.working_dir.clone() };
+        let (file, args, dir_arg) = resolve_launch(link, &a.path, None, None);
+        assert_eq!(file, a.path);
+        assert_eq!(args, None);
+        assert_eq!(dir_arg, None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clickonce_and_urls_cannot_be_elevated() {
+        assert!(ensure_elevatable("C:/Users/me/Start Menu/Contoso App.appref-ms").is_err());
+        assert!(ensure_elevatable("C:/Users/me/Start Menu/CONTOSO.APPREF-MS").is_err());
+        assert!(ensure_elevatable("https://example.com").is_err());
+        assert!(ensure_elevatable("C:/Tools/app.exe").is_ok());
+        assert!(ensure_elevatable("C:/Users/me/Desktop/Editor.lnk").is_ok());
+    }
+
+    #[test]
+    fn powershell_jobs_are_capped() {
+        assert_eq!(POWERSHELL_JOBS.max, MAX_POWERSHELL_JOBS);
+        let slots = JobSlots::new(2);
+        let (first, second) = (slots.acquire(), slots.acquire());
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::scope(|s| {
+            let slots = &slots;
+            s.spawn(move || {
+                let _third = slots.acquire();
+                tx.send(()).unwrap();
+            });
+            assert!(rx.recv_timeout(Duration::from_millis(200)).is_err(), "third job ran alongside two others");
+            drop(first);
+            assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok(), "third job never got the freed slot");
+        });
+        drop(second);
+        assert_eq!(*slots.running.lock().unwrap(), 0);
+    }
+
+    /// `script` run by the platform shell.
+    fn shell(script: &str) -> std::process::Command {
+        let (program, flag) = if cfg!(target_os = "windows") { ("cmd", "/C") } else { ("sh", "-c") };
+        let mut cmd = std::process::Command::new(program);
+        cmd.args([flag, script]);
+        cmd
+    }
+
+    #[test]
+    fn hung_extractors_are_killed() {
+        let slow = if cfg!(target_os = "windows") { "ping -n 6 127.0.0.1 >nul" } else { "sleep 5" };
+        let started = Instant::now();
+        assert!(run_with_timeout(shell(slow), Duration::from_millis(300)).is_none());
+        assert!(started.elapsed() < Duration::from_secs(3), "timed-out job wasn't killed promptly");
+
+        let (success, out) = run_with_timeout(shell("echo ok"), POWERSHELL_TIMEOUT).expect("quick job timed out");
+        assert!(success);
+        assert_eq!(out.trim(), "ok");
+    }
+}